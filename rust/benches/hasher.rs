@@ -0,0 +1,65 @@
+//! `HashMap<u64, _>` throughput with `SmcStdHasher`.
+//!
+//! Run with `cargo bench --bench hasher`. The `write_u64` case exercises the
+//! specialized integer path; `write_bytes` is the generic path it replaces.
+//! `hashmap_entry` uses a fixed-seed map so its layout is identical run to run.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use smchash::{smc_hashmap_with_seed, SmcStdHasher};
+
+fn bench_hasher(c: &mut Criterion) {
+    let keys: Vec<u64> = (0..10_000u64)
+        .map(|i| i.wrapping_mul(0x9e3779b97f4a7c15))
+        .collect();
+
+    c.bench_function("write_u64", |b| {
+        b.iter(|| {
+            let mut acc = 0u64;
+            for &k in &keys {
+                let mut h = SmcStdHasher::new();
+                h.write_u64(black_box(k));
+                acc ^= h.finish();
+            }
+            acc
+        })
+    });
+
+    c.bench_function("write_bytes", |b| {
+        b.iter(|| {
+            let mut acc = 0u64;
+            for &k in &keys {
+                let mut h = SmcStdHasher::new();
+                h.write(&black_box(k).to_le_bytes());
+                acc ^= h.finish();
+            }
+            acc
+        })
+    });
+
+    c.bench_function("hashmap_u64_insert", |b| {
+        b.iter(|| {
+            let mut map: HashMap<u64, u64, BuildHasherDefault<SmcStdHasher>> =
+                HashMap::with_capacity_and_hasher(keys.len(), Default::default());
+            for &k in &keys {
+                map.insert(k, k);
+            }
+            map
+        })
+    });
+
+    c.bench_function("hashmap_entry", |b| {
+        b.iter(|| {
+            let mut map = smc_hashmap_with_seed(42, 1024);
+            for &k in &keys {
+                *map.entry(k & 1023).or_insert(0u64) += 1;
+            }
+            map
+        })
+    });
+}
+
+criterion_group!(benches, bench_hasher);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+//! `smchash` and PRNG throughput across input sizes.
+//!
+//! Run with `cargo bench --bench throughput` (or `-- short` for the 4-16
+//! byte cases alone). Criterion reports bytes/sec for each size, for
+//! comparison with the figures in the top-level README.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use smchash::{fill_bytes, smc_rand, smchash};
+
+const SIZES: [usize; 6] = [8, 16, 64, 128, 1024, 1 << 20];
+
+fn bench_smchash(c: &mut Criterion) {
+    let mut seed = 1u64;
+    let mut data = vec![0u8; 1 << 20];
+    fill_bytes(&mut seed, &mut data);
+
+    let mut group = c.benchmark_group("smchash");
+    for size in SIZES {
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data[..size], |b, d| {
+            b.iter(|| smchash(black_box(d)))
+        });
+    }
+    group.finish();
+}
+
+/// The 8-16 byte class on its own, for short-key workloads; 4 and 7 bytes
+/// take the neighbouring branch for comparison
+fn bench_short(c: &mut Criterion) {
+    let mut seed = 2u64;
+    let mut data = [0u8; 16];
+    fill_bytes(&mut seed, &mut data);
+
+    let mut group = c.benchmark_group("short");
+    for size in [4, 7, 8, 12, 16] {
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data[..size], |b, d| {
+            b.iter(|| smchash(black_box(d)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_rand(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rand");
+
+    group.throughput(Throughput::Bytes(8));
+    group.bench_function("smc_rand", |b| {
+        let mut seed = 0u64;
+        b.iter(|| smc_rand(black_box(&mut seed)))
+    });
+
+    let mut buf = vec![0u8; 1 << 16];
+    group.throughput(Throughput::Bytes(buf.len() as u64));
+    group.bench_function("fill_bytes", |b| {
+        let mut seed = 0u64;
+        b.iter(|| fill_bytes(&mut seed, black_box(&mut buf)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_smchash, bench_short, bench_rand);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+//! Arbitrary input, seed and secret through the one-shot and streaming paths.
+//!
+//! Input layout: 8 bytes of seed, 72 bytes of secret, then one byte giving
+//! the streaming chunk size; everything after that is the data. Shorter
+//! inputs zero-fill the missing fields so every length is exercised.
+//!
+//! Most fuzzed secrets fail `validate_secret`, which debug builds of
+//! `smchash_secret` assert on, so run without `--debug-assertions`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use smchash::{smchash_secret, smchash_seeded, SmcHasher, SmcHasherBuilder};
+
+fuzz_target!(|input: &[u8]| {
+    let mut header = [0u8; 81];
+    let n = input.len().min(header.len());
+    header[..n].copy_from_slice(&input[..n]);
+    let data = &input[n..];
+
+    let seed = u64::from_le_bytes(header[..8].try_into().unwrap());
+    let mut secret = [0u64; 9];
+    for (i, s) in secret.iter_mut().enumerate() {
+        *s = u64::from_le_bytes(header[8 + 8 * i..16 + 8 * i].try_into().unwrap());
+    }
+    let chunk = header[80] as usize + 1;
+
+    let expected = smchash_seeded(data, seed);
+    let mut hasher = SmcHasher::with_seed(seed);
+    for c in data.chunks(chunk) {
+        hasher.update(c);
+    }
+    assert_eq!(hasher.finish(), expected);
+
+    let keyed = smchash_secret(data, seed, &secret);
+    let mut hasher = SmcHasherBuilder::new().seed(seed).secret(secret).build();
+    for c in data.chunks(chunk) {
+        hasher.update(c);
+    }
+    assert_eq!(hasher.finish(), keyed);
+});
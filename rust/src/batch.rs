@@ -0,0 +1,60 @@
+//! Hashing many keys in one call.
+
+use crate::smchash;
+
+/// Hash each of `keys` into the matching slot of `out`.
+///
+/// `out[i]` is set to `smchash(keys[i])`. This currently hashes the keys one
+/// after another; the batch signature leaves room to interleave several keys
+/// internally later without changing callers or results.
+///
+/// # Panics
+///
+/// Panics if `keys` and `out` have different lengths.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash, smchash_batch};
+///
+/// let keys: [&[u8]; 3] = [b"alpha", b"beta", b"gamma"];
+/// let mut out = [0u64; 3];
+/// smchash_batch(&keys, &mut out);
+/// assert_eq!(out[1], smchash(b"beta"));
+/// ```
+pub fn smchash_batch(keys: &[&[u8]], out: &mut [u64]) {
+    assert_eq!(
+        keys.len(),
+        out.len(),
+        "smchash_batch: keys and out must have the same length"
+    );
+    for (slot, key) in out.iter_mut().zip(keys) {
+        *slot = smchash(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_batch_matches_smchash() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 13 + 5) as u8).collect();
+        let keys: Vec<&[u8]> = (0..300).map(|i| &data[i..i + i % 200]).collect();
+        let mut out = alloc::vec![0u64; keys.len()];
+        smchash_batch(&keys, &mut out);
+        for (key, &h) in keys.iter().zip(&out) {
+            assert_eq!(h, smchash(key));
+        }
+
+        smchash_batch(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn test_batch_length_mismatch() {
+        let mut out = [0u64; 1];
+        smchash_batch(&[b"a", b"b"], &mut out);
+    }
+}
@@ -0,0 +1,271 @@
+//! Compile-time hashing.
+
+use crate::{mix, SMC_SECRET};
+
+/// Compute smcHash in a `const` context.
+///
+/// Returns exactly the same value as [`smchash`](fn@crate::smchash) for inputs of
+/// any length, but reads bytes one at a time so it can run at compile time.
+/// At runtime prefer [`smchash`](fn@crate::smchash), which is much faster.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash, smchash_const};
+///
+/// const GET: u64 = smchash_const(b"GET");
+/// assert_eq!(GET, smchash(b"GET"));
+/// ```
+#[must_use]
+pub const fn smchash_const(data: &[u8]) -> u64 {
+    smchash_seeded_const(data, SMC_SECRET[0])
+}
+
+/// Compute smcHash of a fixed-size array in a `const` context.
+///
+/// Same as [`smchash_const`] on the array's bytes, so it supports every `N`,
+/// including the bulk loop above 128 bytes, and equals
+/// [`smchash`](fn@crate::smchash). Useful for `const` lookup tables over
+/// fixed-size keys.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash, smchash_array};
+///
+/// const KEY: [u8; 4] = *b"abcd";
+/// const H: u64 = smchash_array(&KEY);
+/// assert_eq!(H, smchash(&KEY));
+/// ```
+#[must_use]
+pub const fn smchash_array<const N: usize>(data: &[u8; N]) -> u64 {
+    smchash_const(data)
+}
+
+/// Hash a string literal at compile time.
+///
+/// Expands to a [`smchash_const`] call on the literal's bytes, evaluated in
+/// an inline `const` item, so the value is computed by the compiler even when
+/// the macro is used in ordinary code. The result equals
+/// [`smchash`](fn@crate::smchash) on the same bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smchash;
+///
+/// const ROUTE: u64 = smchash::smchash!("route_name");
+/// assert_eq!(ROUTE, smchash(b"route_name"));
+///
+/// match smchash(b"GET") {
+///     h if h == smchash::smchash!("GET") => {}
+///     _ => unreachable!(),
+/// }
+/// ```
+#[macro_export]
+macro_rules! smchash {
+    ($s:literal) => {{
+        const H: u64 = $crate::smchash_const(str::as_bytes($s));
+        H
+    }};
+}
+
+/// `const` counterpart of [`smchash_seeded`](crate::smchash_seeded)
+pub(crate) const fn smchash_seeded_const(data: &[u8], mut seed: u64) -> u64 {
+    let len = data.len();
+    let a: u64;
+    let b: u64;
+
+    if len <= 16 {
+        seed ^= mix(seed ^ SMC_SECRET[0], SMC_SECRET[1] ^ (len as u64));
+
+        if len >= 4 {
+            if len >= 8 {
+                a = read64(data, 0);
+                b = read64(data, len - 8);
+            } else {
+                a = read32(data, 0) as u64;
+                b = read32(data, len - 4) as u64;
+            }
+        } else if len > 0 {
+            a = ((data[0] as u64) << 56) | ((data[len >> 1] as u64) << 32) | (data[len - 1] as u64);
+            b = 0;
+        } else {
+            a = 0;
+            b = 0;
+        }
+
+        return finish(a ^ SMC_SECRET[1], b ^ seed, len);
+    }
+
+    seed ^= mix(seed ^ SMC_SECRET[2], SMC_SECRET[1]);
+    let mut p = 0;
+    let mut i = len;
+
+    if len > 128 {
+        let mut lanes = [seed; 8];
+
+        while i > 128 {
+            let mut k = 0;
+            while k < 8 {
+                lanes[k] = mix(
+                    read64(data, p + 16 * k) ^ SMC_SECRET[k],
+                    read64(data, p + 16 * k + 8) ^ lanes[k],
+                );
+                k += 1;
+            }
+            p += 128;
+            i -= 128;
+        }
+
+        seed = lanes[0] ^ lanes[1] ^ lanes[4] ^ lanes[5];
+        seed ^= lanes[2] ^ lanes[3] ^ lanes[6] ^ lanes[7];
+    }
+
+    if i > 64 {
+        seed = mix(read64(data, p) ^ SMC_SECRET[0], read64(data, p + 8) ^ seed);
+        seed = mix(
+            read64(data, p + 16) ^ SMC_SECRET[1],
+            read64(data, p + 24) ^ seed,
+        );
+        seed = mix(
+            read64(data, p + 32) ^ SMC_SECRET[2],
+            read64(data, p + 40) ^ seed,
+        );
+        seed = mix(
+            read64(data, p + 48) ^ SMC_SECRET[3],
+            read64(data, p + 56) ^ seed,
+        );
+        p += 64;
+        i -= 64;
+    }
+    if i > 32 {
+        seed = mix(read64(data, p) ^ SMC_SECRET[0], read64(data, p + 8) ^ seed);
+        seed = mix(
+            read64(data, p + 16) ^ SMC_SECRET[1],
+            read64(data, p + 24) ^ seed,
+        );
+        p += 32;
+        i -= 32;
+    }
+    if i > 16 {
+        seed = mix(read64(data, p) ^ SMC_SECRET[0], read64(data, p + 8) ^ seed);
+    }
+
+    a = read64(data, len - 16) ^ (len as u64);
+    b = read64(data, len - 8);
+
+    finish(a ^ SMC_SECRET[1], b ^ seed, len)
+}
+
+/// MUM then fold, as at the end of `smchash_seeded`
+const fn finish(a: u64, b: u64, len: usize) -> u64 {
+    let r = (a as u128) * (b as u128);
+    let a = (r as u64) ^ ((r >> 64) as u64);
+    let b = (r >> 64) as u64;
+    mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ (len as u64))
+}
+
+/// Read little-endian u64 at `at`
+const fn read64(p: &[u8], at: usize) -> u64 {
+    let mut v = 0u64;
+    let mut k = 0;
+    while k < 8 {
+        v |= (p[at + k] as u64) << (8 * k);
+        k += 1;
+    }
+    v
+}
+
+/// Read little-endian u32 at `at`
+const fn read32(p: &[u8], at: usize) -> u32 {
+    (p[at] as u32) | (p[at + 1] as u32) << 8 | (p[at + 2] as u32) << 16 | (p[at + 3] as u32) << 24
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{smchash, smchash_seeded};
+
+    #[test]
+    fn test_const_keywords() {
+        const GET: u64 = smchash_const(b"GET");
+        const POST: u64 = smchash_const(b"POST");
+        const EMPTY: u64 = smchash_const(b"");
+        const LONG: u64 = smchash_const(b"Content-Security-Policy-Report-Only");
+        assert_eq!(GET, smchash(b"GET"));
+        assert_eq!(POST, smchash(b"POST"));
+        assert_eq!(EMPTY, smchash(b""));
+        assert_eq!(LONG, smchash(b"Content-Security-Policy-Report-Only"));
+    }
+
+    #[test]
+    fn test_macro() {
+        const TABLE: [u64; 4] = [
+            crate::smchash!(""),
+            crate::smchash!("GET"),
+            crate::smchash!("route_name"),
+            crate::smchash!("a string literal longer than one hundred and twenty-eight bytes, to take the bulk path of the const hash function as well as the tail steps"),
+        ];
+        assert_eq!(TABLE[0], smchash(b""));
+        assert_eq!(TABLE[1], smchash(b"GET"));
+        assert_eq!(TABLE[2], smchash(b"route_name"));
+        assert_eq!(TABLE[3], smchash(b"a string literal longer than one hundred and twenty-eight bytes, to take the bulk path of the const hash function as well as the tail steps"));
+        assert_eq!(crate::smchash!("GET"), smchash_const(b"GET"));
+    }
+
+    /// Byte `i` is `i * 13 + 1`, built at compile time
+    const fn pattern<const N: usize>() -> [u8; N] {
+        let mut out = [0u8; N];
+        let mut i = 0;
+        while i < N {
+            out[i] = (i * 13 + 1) as u8;
+            i += 1;
+        }
+        out
+    }
+
+    #[test]
+    fn test_const_array() {
+        const A0: [u8; 0] = pattern();
+        const A3: [u8; 3] = pattern();
+        const A16: [u8; 16] = pattern();
+        const A17: [u8; 17] = pattern();
+        const A128: [u8; 128] = pattern();
+        const A300: [u8; 300] = pattern();
+        const HASHES: [u64; 6] = [
+            smchash_array(&A0),
+            smchash_array(&A3),
+            smchash_array(&A16),
+            smchash_array(&A17),
+            smchash_array(&A128),
+            smchash_array(&A300),
+        ];
+        let runtime = [
+            smchash(&A0),
+            smchash(&A3),
+            smchash(&A16),
+            smchash(&A17),
+            smchash(&A128),
+            smchash(&A300),
+        ];
+        assert_eq!(HASHES, runtime);
+    }
+
+    #[test]
+    fn test_const_all_lengths() {
+        let data: alloc::vec::Vec<u8> = (0..600u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in 0..data.len() {
+            assert_eq!(
+                smchash_const(&data[..len]),
+                smchash(&data[..len]),
+                "len {}",
+                len
+            );
+            assert_eq!(
+                smchash_seeded_const(&data[..len], 5),
+                smchash_seeded(&data[..len], 5)
+            );
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! [`digest`] integration (requires the `digest` feature).
+
+use digest::consts::U8;
+use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update};
+
+use crate::SmcHasher;
+
+/// smcHash as a [`digest::Digest`] with an 8-byte output.
+///
+/// The output is the little-endian encoding of [`SmcHasher::finish`], i.e. of
+/// [`smchash`](fn@crate::smchash) on everything fed in. This is not a
+/// cryptographic hash; the adapter exists for code that is generic over
+/// `Digest`, such as checksum pipelines.
+///
+/// # Example
+///
+/// ```rust
+/// use digest::Digest;
+/// use smchash::{smchash, Smc64};
+///
+/// let out = Smc64::new().chain_update(b"Hello, ").chain_update(b"World!").finalize();
+/// assert_eq!(out[..], smchash(b"Hello, World!").to_le_bytes());
+/// ```
+#[derive(Default)]
+pub struct Smc64 {
+    inner: SmcHasher,
+}
+
+impl HashMarker for Smc64 {}
+
+impl OutputSizeUser for Smc64 {
+    type OutputSize = U8;
+}
+
+impl Update for Smc64 {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+}
+
+impl FixedOutput for Smc64 {
+    #[inline]
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.inner.finish().to_le_bytes());
+    }
+}
+
+impl FixedOutputReset for Smc64 {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.inner.finish().to_le_bytes());
+        self.reset();
+    }
+}
+
+impl Reset for Smc64 {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner = SmcHasher::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::smchash;
+    use digest::Digest;
+
+    #[test]
+    fn test_digest() {
+        let data: alloc::vec::Vec<u8> = (0..300u32).map(|i| (i * 3) as u8).collect();
+        let out = Smc64::new()
+            .chain_update(&data[..100])
+            .chain_update(&data[100..])
+            .finalize();
+        assert_eq!(out[..], smchash(&data).to_le_bytes());
+        assert_eq!(Smc64::digest(b"")[..], smchash(b"").to_le_bytes());
+        assert_eq!(<Smc64 as Digest>::output_size(), 8);
+    }
+
+    #[test]
+    fn test_digest_reset() {
+        let mut hasher = Smc64::new();
+        Digest::update(&mut hasher, b"discarded");
+        Digest::reset(&mut hasher);
+        Digest::update(&mut hasher, b"abc");
+        assert_eq!(hasher.finalize_reset()[..], smchash(b"abc").to_le_bytes());
+        assert_eq!(hasher.finalize()[..], smchash(b"").to_le_bytes());
+    }
+}
@@ -0,0 +1,79 @@
+//! OS-entropy seeding (requires the `getrandom` feature).
+//!
+//! Without this feature the crate never touches an entropy source on its
+//! own (apart from the `std`-only `SmcRandomState` and `random`), and seeds
+//! and secrets must be supplied by the caller.
+
+use crate::{smc_make_secret, validate_secret};
+
+/// Draw a 64-bit seed from the operating system's secure RNG.
+///
+/// Suitable for seeding [`SmcRng`](crate::SmcRng), [`smchash_seeded`](crate::smchash_seeded)
+/// or [`SmcBuildHasher::with_seed`](crate::SmcBuildHasher::with_seed) with a
+/// per-process value an attacker cannot predict.
+///
+/// # Panics
+///
+/// Panics if the OS entropy source is unavailable, which `getrandom` only
+/// reports on misconfigured or very early-boot systems.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use smchash::{random_seed, SmcBuildHasher};
+///
+/// let mut map = HashMap::with_hasher(SmcBuildHasher::with_seed(random_seed()));
+/// map.insert("key", 1);
+/// ```
+#[must_use]
+pub fn random_seed() -> u64 {
+    let mut buf = [0u8; 8];
+    getrandom::getrandom(&mut buf).expect("random_seed: OS entropy source unavailable");
+    u64::from_le_bytes(buf)
+}
+
+/// Generate a custom secret from OS entropy.
+///
+/// Runs [`smc_make_secret`] on a [`random_seed`] and checks the result with
+/// [`validate_secret`], retrying with a new seed in the (never observed)
+/// case that it fails. Generation takes around a second; do it once per
+/// process.
+///
+/// # Panics
+///
+/// Panics if the OS entropy source is unavailable.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use smchash::{random_secret, smchash_secret};
+///
+/// let secret = random_secret();
+/// let hash = smchash_secret(b"data", 0, &secret);
+/// ```
+#[must_use]
+pub fn random_secret() -> [u64; 9] {
+    loop {
+        let secret = smc_make_secret(random_seed());
+        if validate_secret(&secret).is_ok() {
+            return secret;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_seed() {
+        assert_ne!(random_seed(), random_seed());
+    }
+
+    #[test]
+    fn test_random_secret() {
+        let secret = random_secret();
+        assert_eq!(validate_secret(&secret), Ok(()));
+    }
+}
@@ -0,0 +1,125 @@
+//! Crate-wide error type.
+
+use core::fmt;
+
+use crate::SecretError;
+
+/// Error returned by the crate's fallible parsing and validation APIs.
+///
+/// Works without `std`: it implements [`Display`](fmt::Display), plus
+/// `std::error::Error` with the `std` feature. [`SecretError`] converts into
+/// it, so `?` can combine secret validation with other checks in one
+/// function.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{validate_secret, Hash64, SmcError};
+///
+/// fn load(secret: &[u64; 9], expected: &str) -> Result<Hash64, SmcError> {
+///     validate_secret(secret)?;
+///     expected.parse()
+/// }
+///
+/// let secret = [
+///     0xf03aa5f0392b74c3, 0xa6ca3378b4cc6c35, 0xe1274b4b960f1ec5,
+///     0xe46cac0f4d8d5933, 0xd4b8478d3cd42bc9, 0xa3d24bc96335711b,
+///     0xb85a9a1e5578178d, 0x636596992ee8b499, 0x635cd16a99d18d4b,
+/// ];
+/// assert_eq!(load(&secret, "25bb0982c5c0de6e"), Ok(Hash64(0x25bb0982c5c0de6e)));
+/// assert_eq!(
+///     load(&secret, "25bb0982"),
+///     Err(SmcError::LengthMismatch { expected: 16, found: 8 })
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SmcError {
+    /// A secret failed [`validate_secret`](crate::validate_secret)
+    InvalidSecret(SecretError),
+    /// Input contained a character that is not a hex digit
+    InvalidHex,
+    /// Input had `found` hex digits where `expected` were required
+    LengthMismatch { expected: usize, found: usize },
+}
+
+impl fmt::Display for SmcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SmcError::InvalidSecret(e) => write!(f, "invalid secret: {}", e),
+            SmcError::InvalidHex => f.write_str("invalid hex digit"),
+            SmcError::LengthMismatch { expected, found } => {
+                write!(f, "expected {} hex digits, found {}", expected, found)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SmcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SmcError::InvalidSecret(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<SecretError> for SmcError {
+    #[inline]
+    fn from(e: SecretError) -> Self {
+        SmcError::InvalidSecret(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{validate_secret, Hash128, Hash64};
+    use alloc::string::ToString;
+
+    fn check(secret: &[u64; 9]) -> Result<(), SmcError> {
+        validate_secret(secret)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_variants() {
+        let mut secret = crate::smc_make_secret(0);
+        assert_eq!(check(&secret), Ok(()));
+        secret[2] ^= 1;
+        assert_eq!(
+            check(&secret),
+            Err(SmcError::InvalidSecret(SecretError::NotOdd { index: 2 }))
+        );
+
+        assert_eq!(
+            "25bb0982c5c0de6g".parse::<Hash64>(),
+            Err(SmcError::InvalidHex)
+        );
+        assert_eq!(
+            "0x25bb0982c5c0de".parse::<Hash64>(),
+            Err(SmcError::InvalidHex)
+        );
+        assert_eq!(
+            "25bb".parse::<Hash128>(),
+            Err(SmcError::LengthMismatch {
+                expected: 32,
+                found: 4
+            })
+        );
+
+        assert_eq!(SmcError::InvalidHex.to_string(), "invalid hex digit");
+        assert_eq!(
+            SmcError::LengthMismatch {
+                expected: 16,
+                found: 8
+            }
+            .to_string(),
+            "expected 16 hex digits, found 8"
+        );
+        assert_eq!(
+            SmcError::from(SecretError::NotPrime { index: 1 }).to_string(),
+            "invalid secret: secret[1] is not prime"
+        );
+    }
+}
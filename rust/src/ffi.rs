@@ -0,0 +1,141 @@
+//! C ABI exports (requires the `ffi` feature).
+//!
+//! Build a shared or static library for C and C++ with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or
+//! `staticlib`). The matching declarations are:
+//!
+//! ```c
+//! uint64_t smchash_ffi(const uint8_t *data, size_t len);
+//! uint64_t smchash_seeded_ffi(const uint8_t *data, size_t len, uint64_t seed);
+//! uint64_t smchash_secret_ffi(const uint8_t *data, size_t len, uint64_t seed,
+//!                             const uint64_t secret[9]);
+//! ```
+//!
+//! Each export returns exactly what the Rust function it wraps returns. Against
+//! the C header implementation (`smchash.h`):
+//!
+//! - `smchash_ffi` equals C `smchash()` for every input.
+//! - `smchash_secret_ffi` equals C `smchash_secret()` for every input.
+//! - `smchash_seeded_ffi` equals C `smchash_seeded()` only up to 16 bytes.
+//!   Above that, C derives the seed from `SMC_SECRET[0]` and this crate from
+//!   `SMC_SECRET[2]`, so the two give different hashes for the same seed. C++
+//!   code that must agree with the header's `smchash_seeded` should call
+//!   `smchash_secret_ffi` with the header's `SMC_SECRET`, which computes the
+//!   C seeded function exactly.
+
+use core::slice;
+
+use crate::{smchash_secret_unchecked, smchash_seeded, SMC_SECRET};
+
+/// View `(data, len)` as a slice, treating a null pointer with `len == 0` as
+/// empty input.
+///
+/// # Safety
+///
+/// Unless `len` is zero, `data` must be valid for reads of `len` bytes.
+#[inline]
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        // SAFETY: guaranteed by the caller
+        unsafe { slice::from_raw_parts(data, len) }
+    }
+}
+
+/// C ABI version of [`smchash`](fn@crate::smchash).
+///
+/// # Safety
+///
+/// Unless `len` is zero, `data` must be non-null and valid for reads of `len`
+/// bytes for the duration of the call. `data` may be null when `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn smchash_ffi(data: *const u8, len: usize) -> u64 {
+    // SAFETY: forwarded from the caller
+    smchash_seeded(unsafe { bytes(data, len) }, SMC_SECRET[0])
+}
+
+/// C ABI version of [`smchash_seeded`].
+///
+/// Differs from the C header's `smchash_seeded` above 16 bytes; see the
+/// module docs.
+///
+/// # Safety
+///
+/// Same contract as [`smchash_ffi`].
+#[no_mangle]
+pub unsafe extern "C" fn smchash_seeded_ffi(data: *const u8, len: usize, seed: u64) -> u64 {
+    // SAFETY: forwarded from the caller
+    smchash_seeded(unsafe { bytes(data, len) }, seed)
+}
+
+/// C ABI version of [`smchash_secret`](crate::smchash_secret).
+///
+/// Unlike the Rust function, this does not check the secret with
+/// [`validate_secret`](crate::validate_secret) in debug builds: a panic
+/// cannot unwind out of an `extern "C"` function and would abort the host
+/// process. Any 9 values are hashed as given.
+///
+/// # Safety
+///
+/// Same contract as [`smchash_ffi`] for `data` and `len`. `secret` must be
+/// non-null and point to 9 readable `u64` values (any alignment valid for
+/// `u64`).
+#[no_mangle]
+pub unsafe extern "C" fn smchash_secret_ffi(
+    data: *const u8,
+    len: usize,
+    seed: u64,
+    secret: *const u64,
+) -> u64 {
+    // SAFETY: forwarded from the caller
+    let (data, secret) = unsafe { (bytes(data, len), &*(secret as *const [u64; 9])) };
+    smchash_secret_unchecked(data, seed, secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{smchash, smchash_secret};
+    use core::ptr;
+
+    #[test]
+    fn test_ffi_matches_safe_api() {
+        let data: alloc::vec::Vec<u8> = (0..300u32).map(|i| (i * 3) as u8).collect();
+        let secret = SMC_SECRET;
+        for len in [0, 1, 16, 17, 128, 129, 300] {
+            let d = &data[..len];
+            unsafe {
+                assert_eq!(smchash_ffi(d.as_ptr(), len), smchash(d));
+                assert_eq!(smchash_seeded_ffi(d.as_ptr(), len, 5), smchash_seeded(d, 5));
+                assert_eq!(
+                    smchash_secret_ffi(d.as_ptr(), len, 5, secret.as_ptr()),
+                    smchash_secret(d, 5, &secret)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_ffi_null_empty() {
+        unsafe {
+            assert_eq!(smchash_ffi(ptr::null(), 0), smchash(b""));
+            assert_eq!(
+                smchash_seeded_ffi(ptr::null(), 0, 9),
+                smchash_seeded(b"", 9)
+            );
+            assert_eq!(
+                smchash_secret_ffi(ptr::null(), 0, 9, SMC_SECRET.as_ptr()),
+                smchash_secret(b"", 9, &SMC_SECRET)
+            );
+        }
+    }
+
+    #[test]
+    fn test_ffi_unvalidated_secret() {
+        // Fails validate_secret, but must not trip the debug check
+        let secret = [1u64; 9];
+        let h = unsafe { smchash_secret_ffi(b"data".as_ptr(), 4, 0, secret.as_ptr()) };
+        assert_eq!(h, smchash_secret_unchecked(b"data", 0, &secret));
+    }
+}
@@ -0,0 +1,561 @@
+//! [`core::hash::Hasher`] integration.
+
+use core::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+
+use crate::{mix, smchash_seeded, SmcHasher, SMC_SECRET};
+
+/// smcHash as a [`Hasher`] for use with `HashMap`, `HashSet` and friends.
+///
+/// Every call to [`write`](Hasher::write) feeds the streaming state, so the
+/// result of [`finish`](Hasher::finish) equals [`smchash_seeded`](crate::smchash_seeded)
+/// on the concatenation of all written bytes. The integer methods
+/// (`write_u64` etc.) skip the generic byte-slice path but hash exactly like
+/// writing the integer's little-endian bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use core::hash::{BuildHasherDefault, Hasher};
+/// use std::collections::HashMap;
+/// use smchash::{smchash, SmcStdHasher};
+///
+/// let mut hasher = SmcStdHasher::new();
+/// hasher.write(b"Hello, ");
+/// hasher.write(b"World!");
+/// assert_eq!(hasher.finish(), smchash(b"Hello, World!"));
+///
+/// let mut map: HashMap<&str, u32, BuildHasherDefault<SmcStdHasher>> = HashMap::default();
+/// map.insert("answer", 42);
+/// assert_eq!(map["answer"], 42);
+/// ```
+#[derive(Clone, Default)]
+pub struct SmcStdHasher {
+    inner: SmcHasher,
+}
+
+impl SmcStdHasher {
+    /// Create a hasher using the default seed.
+    #[inline]
+    pub fn new() -> Self {
+        SmcStdHasher {
+            inner: SmcHasher::new(),
+        }
+    }
+
+    /// Create a hasher with a custom seed.
+    #[inline]
+    pub fn with_seed(seed: u64) -> Self {
+        SmcStdHasher {
+            inner: SmcHasher::with_seed(seed),
+        }
+    }
+}
+
+impl Hasher for SmcStdHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.inner.update_word(i as u64, 1);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.inner.update_word(i as u64, 2);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.inner.update_word(i as u64, 4);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.inner.update_word(i, 8);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.inner
+            .update_word(i as u64, core::mem::size_of::<usize>());
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+}
+
+/// Hash any `T: Hash` with smcHash.
+///
+/// Feeds `value` through its own [`Hash`] impl into
+/// [`SmcStdHasher::with_seed`] and returns the finished hash, so the result
+/// matches what an [`SmcBuildHasher`] with the same seed would produce. Field
+/// order and prefix-freedom are up to the type's `Hash` impl; derived impls
+/// handle both.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::hash_of;
+///
+/// #[derive(Hash)]
+/// struct Key {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// let k = Key { id: 7, name: "alice".into() };
+/// assert_eq!(hash_of(&k, 42), hash_of(&k, 42));
+/// assert_ne!(hash_of(&k, 42), hash_of(&k, 43));
+/// ```
+#[inline]
+#[must_use]
+pub fn hash_of<T: Hash + ?Sized>(value: &T, seed: u64) -> u64 {
+    let mut hasher = SmcStdHasher::with_seed(seed);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// [`BuildHasher`] producing [`SmcStdHasher`]s with a fixed seed.
+///
+/// Maps built with the same seed hash keys identically, which makes this the
+/// right choice for reproducible behaviour (e.g. in tests). For HashDoS
+/// resistance use `SmcRandomState` (requires the `std` feature) instead.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use smchash::SmcBuildHasher;
+///
+/// let mut map = HashMap::with_hasher(SmcBuildHasher::with_seed(42));
+/// map.insert("key", "value");
+/// assert_eq!(map.get("key"), Some(&"value"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SmcBuildHasher {
+    seed: u64,
+}
+
+impl SmcBuildHasher {
+    /// Create a build hasher using the default seed.
+    #[inline]
+    pub const fn new() -> Self {
+        SmcBuildHasher {
+            seed: SMC_SECRET[0],
+        }
+    }
+
+    /// Create a build hasher with a custom seed.
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        SmcBuildHasher { seed }
+    }
+}
+
+impl Default for SmcBuildHasher {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for SmcBuildHasher {
+    type Hasher = SmcStdHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> SmcStdHasher {
+        SmcStdHasher::with_seed(self.seed)
+    }
+}
+
+/// [`BuildHasher`] with a random per-instance seed.
+///
+/// Each instance draws its seed from process entropy, so two maps built with
+/// different `SmcRandomState` instances will place keys differently. This
+/// makes collisions crafted against one map useless against another.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use smchash::SmcRandomState;
+///
+/// let mut map: HashMap<&str, i32, SmcRandomState> = HashMap::default();
+/// map.insert("key", 1);
+/// assert_eq!(map["key"], 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Copy)]
+pub struct SmcRandomState {
+    seed: u64,
+}
+
+/// Omits the seed, which must stay secret for HashDoS resistance
+#[cfg(feature = "std")]
+impl core::fmt::Debug for SmcRandomState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SmcRandomState").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl SmcRandomState {
+    /// Create a build hasher with a fresh random seed.
+    pub fn new() -> Self {
+        let seed = crate::random::entropy_seed();
+        SmcRandomState { seed }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SmcRandomState {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl BuildHasher for SmcRandomState {
+    type Hasher = SmcStdHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> SmcStdHasher {
+        SmcStdHasher::with_seed(self.seed)
+    }
+}
+
+/// Pass-through [`Hasher`] for keys that are already hashes.
+///
+/// A single integer write (`write_u64` and the narrower `write_*` methods,
+/// which is all `Hash` does for integer keys) is returned unchanged by
+/// [`finish`](Hasher::finish), so a map keyed by smcHash outputs does not
+/// hash them a second time. Hash tables use both the low and the high bits
+/// of the hash, so only use this for keys that are already uniformly
+/// distributed; use [`SmcBuildHasher`] for anything else.
+///
+/// Further writes (e.g. tuple keys) mix the previous state in, and byte
+/// writes are hashed with [`smchash_seeded`](crate::smchash_seeded), so any
+/// key type still hashes correctly, just without the shortcut.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use smchash::{smchash, SmcIdentityBuildHasher};
+///
+/// let mut cache: HashMap<u64, &str, SmcIdentityBuildHasher> = HashMap::default();
+/// cache.insert(smchash(b"alpha"), "alpha");
+/// assert_eq!(cache[&smchash(b"alpha")], "alpha");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmcIdentityHasher {
+    hash: u64,
+}
+
+impl Hasher for SmcIdentityHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = smchash_seeded(bytes, self.hash);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        // mix(0, _) is 0, so the first write passes through unchanged
+        self.hash = mix(self.hash, SMC_SECRET[3]) ^ i;
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// [`BuildHasher`] producing [`SmcIdentityHasher`]s.
+pub type SmcIdentityBuildHasher = BuildHasherDefault<SmcIdentityHasher>;
+
+/// `std::collections::HashMap` using [`SmcBuildHasher`].
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::SmcHashMap;
+///
+/// let mut map = SmcHashMap::default();
+/// map.insert("key", 1);
+/// assert_eq!(map.get("key"), Some(&1));
+/// ```
+#[cfg(feature = "std")]
+pub type SmcHashMap<K, V> = std::collections::HashMap<K, V, SmcBuildHasher>;
+
+/// `std::collections::HashSet` using [`SmcBuildHasher`].
+#[cfg(feature = "std")]
+pub type SmcHashSet<T> = std::collections::HashSet<T, SmcBuildHasher>;
+
+/// Create an empty [`SmcHashMap`] with a fixed seed and room for `cap` entries.
+///
+/// Shorthand for `HashMap::with_capacity_and_hasher(cap, SmcBuildHasher::with_seed(seed))`.
+/// Two maps built with the same seed and fed the same inserts in the same
+/// order iterate identically, which keeps benchmarks and tests reproducible.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smc_hashmap_with_seed;
+///
+/// let mut map = smc_hashmap_with_seed(42, 1024);
+/// *map.entry("hits").or_insert(0) += 1;
+/// assert_eq!(map["hits"], 1);
+/// assert!(map.capacity() >= 1024);
+/// ```
+#[cfg(feature = "std")]
+pub fn smc_hashmap_with_seed<K, V>(seed: u64, cap: usize) -> SmcHashMap<K, V> {
+    std::collections::HashMap::with_capacity_and_hasher(cap, SmcBuildHasher::with_seed(seed))
+}
+
+/// `hashbrown::HashMap` using [`SmcBuildHasher`] (requires the `hashbrown`
+/// feature; works without `std`).
+#[cfg(feature = "hashbrown")]
+pub type SmcHashbrownMap<K, V> = hashbrown::HashMap<K, V, SmcBuildHasher>;
+
+/// `hashbrown::HashSet` using [`SmcBuildHasher`] (requires the `hashbrown`
+/// feature; works without `std`).
+#[cfg(feature = "hashbrown")]
+pub type SmcHashbrownSet<T> = hashbrown::HashSet<T, SmcBuildHasher>;
+
+/// `indexmap::IndexMap` using [`SmcBuildHasher`] (requires the `indexmap`
+/// feature; works without `std`).
+#[cfg(feature = "indexmap")]
+pub type SmcIndexMap<K, V> = indexmap::IndexMap<K, V, SmcBuildHasher>;
+
+/// `indexmap::IndexSet` using [`SmcBuildHasher`] (requires the `indexmap`
+/// feature; works without `std`).
+#[cfg(feature = "indexmap")]
+pub type SmcIndexSet<T> = indexmap::IndexSet<T, SmcBuildHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{smchash, smchash_seeded};
+    #[cfg(feature = "std")]
+    use core::hash::BuildHasherDefault;
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_hash_of() {
+        use alloc::string::String;
+
+        let key = (7u32, String::from("alice"));
+        let h = hash_of(&key, 42);
+        assert_eq!(h, hash_of(&(7u32, String::from("alice")), 42));
+        assert_ne!(h, hash_of(&key, 43));
+        assert_ne!(h, hash_of(&(8u32, String::from("alice")), 42));
+        assert_ne!(h, hash_of(&(7u32, String::from("alicf")), 42));
+        assert_eq!(h, SmcBuildHasher::with_seed(42).hash_one(&key));
+        assert_eq!(hash_of("alice", 42), hash_of(&String::from("alice"), 42));
+    }
+
+    #[test]
+    fn test_hasher_multiple_writes() {
+        let data: alloc::vec::Vec<u8> = (0..500u32).map(|i| (i * 13) as u8).collect();
+        let mut hasher = SmcStdHasher::with_seed(7);
+        for chunk in data.chunks(33) {
+            hasher.write(chunk);
+        }
+        assert_eq!(hasher.finish(), smchash_seeded(&data, 7));
+
+        let mut hasher = SmcStdHasher::new();
+        assert_eq!(hasher.finish(), smchash(b""));
+        hasher.write(b"abc");
+        hasher.write(b"");
+        hasher.write(b"def");
+        assert_eq!(hasher.finish(), smchash(b"abcdef"));
+    }
+
+    #[test]
+    fn test_hasher_integers() {
+        let mut seed = 5u64;
+        for _ in 0..100 {
+            let x = crate::smc_rand(&mut seed);
+            // Interleave with a byte write so later integers straddle block edges
+            let pad = (x % 200) as usize;
+            let padding = [0xa5u8; 200];
+
+            let mut fast = SmcStdHasher::with_seed(x);
+            let mut slow = SmcStdHasher::with_seed(x);
+            fast.write(&padding[..pad]);
+            slow.write(&padding[..pad]);
+
+            fast.write_u8(x as u8);
+            slow.write(&(x as u8).to_le_bytes());
+            fast.write_u16(x as u16);
+            slow.write(&(x as u16).to_le_bytes());
+            fast.write_u32(x as u32);
+            slow.write(&(x as u32).to_le_bytes());
+            fast.write_u64(x);
+            slow.write(&x.to_le_bytes());
+            fast.write_usize(x as usize);
+            slow.write(&(x as usize).to_le_bytes());
+            assert_eq!(fast.finish(), slow.finish());
+        }
+
+        let mut hasher = SmcStdHasher::new();
+        hasher.write_u64(42);
+        assert_eq!(hasher.finish(), smchash(&42u64.to_le_bytes()));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hasher_hashmap() {
+        let mut map: HashMap<u64, u64, BuildHasherDefault<SmcStdHasher>> = HashMap::default();
+        for i in 0..1000 {
+            map.insert(i, i * 2);
+        }
+        for i in 0..1000 {
+            assert_eq!(map[&i], i * 2);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_build_hasher_map() {
+        let mut map = HashMap::with_hasher(SmcBuildHasher::with_seed(1));
+        for i in 0..10_000u32 {
+            map.insert(i, i ^ 0x5555);
+        }
+        assert_eq!(map.len(), 10_000);
+        for i in 0..10_000u32 {
+            assert_eq!(map.get(&i), Some(&(i ^ 0x5555)));
+        }
+        assert_eq!(map.get(&10_000), None);
+    }
+
+    #[test]
+    fn test_build_hasher_seed() {
+        let a = SmcBuildHasher::with_seed(1).hash_one("key");
+        let b = SmcBuildHasher::with_seed(1).hash_one("key");
+        let c = SmcBuildHasher::with_seed(2).hash_one("key");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(
+            SmcBuildHasher::new().hash_one(7u64),
+            SmcBuildHasher::default().hash_one(7u64)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_random_state_map() {
+        let mut map: HashMap<alloc::string::String, usize, SmcRandomState> = HashMap::default();
+        for i in 0..10_000 {
+            map.insert(alloc::format!("key{}", i), i);
+        }
+        for i in 0..10_000 {
+            assert_eq!(map[&alloc::format!("key{}", i)], i);
+        }
+
+        let s1 = SmcRandomState::new();
+        let s2 = SmcRandomState::new();
+        assert_ne!(s1.hash_one("key"), s2.hash_one("key"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_random_state_debug_hides_seed() {
+        let state = SmcRandomState::new();
+        let shown = alloc::format!("{:?}", state);
+        assert_eq!(shown, "SmcRandomState { .. }");
+        assert!(!shown.contains(&alloc::format!("{}", state.seed)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hashmap_with_seed() {
+        let build = |seed| {
+            let mut map = smc_hashmap_with_seed(seed, 500);
+            assert!(map.capacity() >= 500);
+            for i in 0..500u32 {
+                map.insert(i, i * 3);
+            }
+            map
+        };
+        let a = build(9);
+        for i in 0..500u32 {
+            assert_eq!(a[&i], i * 3);
+        }
+
+        let order = |m: &SmcHashMap<u32, u32>| m.keys().copied().collect::<alloc::vec::Vec<_>>();
+        assert_eq!(order(&a), order(&build(9)));
+        assert_ne!(order(&a), order(&build(10)));
+    }
+
+    #[test]
+    fn test_identity_hasher() {
+        let h = smchash(b"key");
+        assert_eq!(SmcIdentityBuildHasher::default().hash_one(h), h);
+        assert_eq!(SmcIdentityBuildHasher::default().hash_one(7u32), 7);
+
+        // Tuples and byte keys still hash consistently and apart
+        let build = SmcIdentityBuildHasher::default();
+        assert_ne!(build.hash_one((1u64, 2u64)), build.hash_one((2u64, 1u64)));
+        assert_eq!(build.hash_one("abc"), build.hash_one("abc"));
+        assert_ne!(build.hash_one("abc"), build.hash_one("abd"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_identity_hasher_map() {
+        let mut map: HashMap<u64, u32, SmcIdentityBuildHasher> = HashMap::default();
+        let keys: alloc::vec::Vec<u64> =
+            (0..10_000u32).map(|i| smchash(&i.to_le_bytes())).collect();
+        for (i, &k) in keys.iter().enumerate() {
+            map.insert(k, i as u32);
+        }
+        assert_eq!(map.len(), keys.len());
+        for (i, &k) in keys.iter().enumerate() {
+            assert_eq!(map[&k], i as u32);
+        }
+        assert_eq!(map.get(&smchash(b"missing")), None);
+    }
+
+    #[cfg(all(feature = "hashbrown", feature = "indexmap"))]
+    #[test]
+    fn test_map_aliases() {
+        let mut map = SmcHashbrownMap::default();
+        let mut index = SmcIndexMap::default();
+        for i in 0..100u32 {
+            map.insert(i, i + 1);
+            index.insert(i, i + 1);
+        }
+        assert_eq!(map.get(&7), Some(&8));
+        assert_eq!(index.get_index(7), Some((&7, &8)));
+        assert!(SmcHashbrownSet::<u8>::default().insert(1));
+        assert!(SmcIndexSet::<u8>::default().insert(1));
+    }
+}
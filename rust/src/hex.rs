@@ -0,0 +1,109 @@
+//! Hex formatting and parsing of hash values.
+//!
+//! The formatting functions return fixed-size arrays so they work without an
+//! allocator; use `core::str::from_utf8` to view the result as a `&str`.
+
+use crate::SmcError;
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Format a 64-bit hash as 16 lowercase, zero-padded hex digits.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{hash_from_hex, hash_to_hex};
+///
+/// let hex = hash_to_hex(0x25bb0982c5c0de6e);
+/// assert_eq!(&hex, b"25bb0982c5c0de6e");
+/// assert_eq!(hash_from_hex(core::str::from_utf8(&hex).unwrap()), Ok(0x25bb0982c5c0de6e));
+/// ```
+#[must_use]
+pub fn hash_to_hex(h: u64) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    for (i, c) in out.iter_mut().enumerate() {
+        *c = DIGITS[((h >> (60 - 4 * i)) & 0xf) as usize];
+    }
+    out
+}
+
+/// Parse a 64-bit hash from exactly 16 hex digits (either case).
+///
+/// Returns [`SmcError::LengthMismatch`] for any other length and
+/// [`SmcError::InvalidHex`] for non-hex characters, including signs and `0x`
+/// prefixes.
+pub fn hash_from_hex(s: &str) -> Result<u64, SmcError> {
+    parse_hex(s, 16).map(|v| v as u64)
+}
+
+/// Format a 128-bit hash as 32 lowercase, zero-padded hex digits.
+#[must_use]
+pub fn hash128_to_hex(h: u128) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[..16].copy_from_slice(&hash_to_hex((h >> 64) as u64));
+    out[16..].copy_from_slice(&hash_to_hex(h as u64));
+    out
+}
+
+/// Parse a 128-bit hash from exactly 32 hex digits (either case).
+///
+/// Fails like [`hash_from_hex`].
+pub fn hash128_from_hex(s: &str) -> Result<u128, SmcError> {
+    parse_hex(s, 32)
+}
+
+/// Parse exactly `len` hex digits (at most 32), reporting why parsing failed
+pub(crate) fn parse_hex(s: &str, len: usize) -> Result<u128, SmcError> {
+    if s.len() != len {
+        return Err(SmcError::LengthMismatch {
+            expected: len,
+            found: s.len(),
+        });
+    }
+    let mut v = 0u128;
+    for c in s.bytes() {
+        let d = (c as char).to_digit(16).ok_or(SmcError::InvalidHex)?;
+        v = (v << 4) | d as u128;
+    }
+    Ok(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_roundtrip() {
+        for &h in &[0u64, 1, 0x25bb0982c5c0de6e, u64::MAX] {
+            let hex = hash_to_hex(h);
+            assert_eq!(hash_from_hex(core::str::from_utf8(&hex).unwrap()), Ok(h));
+        }
+        assert_eq!(&hash_to_hex(1), b"0000000000000001");
+        assert_eq!(hash_from_hex("25BB0982C5C0DE6E"), Ok(0x25bb0982c5c0de6e));
+
+        let h = 0x0123456789abcdef_fedcba9876543210u128;
+        let hex = hash128_to_hex(h);
+        assert_eq!(&hex, b"0123456789abcdeffedcba9876543210");
+        assert_eq!(hash128_from_hex(core::str::from_utf8(&hex).unwrap()), Ok(h));
+    }
+
+    #[test]
+    fn test_hex_reject() {
+        let len = |expected, found| Err(SmcError::LengthMismatch { expected, found });
+        assert_eq!(hash_from_hex(""), len(16, 0));
+        assert_eq!(hash_from_hex("123"), len(16, 3));
+        assert_eq!(hash_from_hex("00000000000000001"), len(16, 17));
+        assert_eq!(hash_from_hex("000000000000000g"), Err(SmcError::InvalidHex));
+        assert_eq!(hash_from_hex("+000000000000001"), Err(SmcError::InvalidHex));
+        assert_eq!(hash_from_hex("0x00000000000001"), Err(SmcError::InvalidHex));
+        // 15 characters but 16 bytes
+        assert_eq!(hash_from_hex("00000000000000é"), Err(SmcError::InvalidHex));
+        assert_eq!(
+            hash128_from_hex("0000000000000001"),
+            Err(SmcError::LengthMismatch {
+                expected: 32,
+                found: 16
+            })
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Hashing from `std::io` sources (requires the `std` feature).
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::vec;
+
+use crate::{fill_bytes, SmcHasher};
+#[cfg(feature = "mmap")]
+use std::{fs::File, path::Path};
+
+/// Read buffer size for [`hash_reader`]
+const READ_BUF: usize = 64 * 1024;
+
+/// Hash everything `reader` yields until end of file.
+///
+/// Reads in 64 KiB chunks through the streaming hasher, so memory use does not
+/// grow with the input. Returns the same value as [`smchash`](fn@crate::smchash)
+/// on the full contents. Short reads are fine and reads interrupted with
+/// [`ErrorKind::Interrupted`] are retried; any other error is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use smchash::{hash_reader, smchash};
+///
+/// let data = vec![7u8; 1000];
+/// assert_eq!(hash_reader(Cursor::new(&data)).unwrap(), smchash(&data));
+/// ```
+pub fn hash_reader<R: Read>(mut reader: R) -> io::Result<u64> {
+    let mut hasher = SmcHasher::new();
+    let mut buf = vec![0u8; READ_BUF];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return Ok(hasher.finish()),
+            Ok(n) => hasher.update(&buf[..n]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// An [`io::Write`] sink that hashes everything written to it.
+///
+/// Every write is accepted in full and fed to an [`SmcHasher`]; `flush` does
+/// nothing. [`finish`](Self::finish) returns the same value as
+/// [`smchash`](fn@crate::smchash) (or `smchash_seeded` with
+/// [`with_seed`](Self::with_seed)) on all bytes written so far.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{self, Cursor};
+/// use smchash::{smchash, HashingWriter};
+///
+/// let data = vec![7u8; 1000];
+/// let mut writer = HashingWriter::new();
+/// io::copy(&mut Cursor::new(&data), &mut writer)?;
+/// assert_eq!(writer.finish(), smchash(&data));
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct HashingWriter {
+    hasher: SmcHasher,
+}
+
+impl HashingWriter {
+    /// Create a writer using the default seed.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a writer with a custom seed.
+    #[inline]
+    pub fn with_seed(seed: u64) -> Self {
+        HashingWriter {
+            hasher: SmcHasher::with_seed(seed),
+        }
+    }
+
+    /// Return the hash of all bytes written so far.
+    #[inline]
+    #[must_use]
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+
+impl Write for HashingWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hasher.update(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.hasher.update(buf);
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Write `bytes` bytes of PRNG output starting from `seed`.
+///
+/// The stream is exactly what [`fill_bytes`] produces from the same seed:
+/// each [`smc_rand`](crate::smc_rand) output as 8 little-endian bytes. It is
+/// meant for re-running statistical test suites on your own platform, e.g.
+/// piping into PractRand's `RNG_test stdin64` or feeding TestU01 from a file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::io;
+///
+/// // Run as `./gen | RNG_test stdin64`
+/// smchash::write_rand_stream(42, 1 << 30, &mut io::stdout().lock())?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn write_rand_stream<W: Write>(mut seed: u64, bytes: usize, out: &mut W) -> io::Result<()> {
+    let mut buf = vec![0u8; READ_BUF];
+    let mut left = bytes;
+    while left > 0 {
+        // READ_BUF is a multiple of 8, so chunking does not change the stream
+        let n = left.min(READ_BUF);
+        fill_bytes(&mut seed, &mut buf[..n]);
+        out.write_all(&buf[..n])?;
+        left -= n;
+    }
+    Ok(())
+}
+
+/// Hash the contents of a file (requires the `mmap` feature).
+///
+/// The file is memory-mapped and hashed with a single [`smchash`](fn@crate::smchash)
+/// call, which is the fastest option for large files. If the file cannot be
+/// mapped it is hashed with [`hash_reader`] instead; either way the result
+/// equals [`smchash`](fn@crate::smchash) on the file contents. Files that
+/// report a length of 0 are never mapped but read with [`hash_reader`]: truly
+/// empty files hash like `smchash(b"")`, and procfs/sysfs files, FIFOs and
+/// devices that report 0 while holding data hash their contents.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// let hash = smchash::smchash_file("large.bin")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "mmap")]
+pub fn smchash_file<P: AsRef<Path>>(path: P) -> io::Result<u64> {
+    let file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return hash_reader(file);
+    }
+    // SAFETY: the map is only read for the duration of this call. Like every
+    // file mapping it is undefined behaviour if another process truncates or
+    // writes the file meanwhile; callers hashing files that may change
+    // concurrently should use `hash_reader` instead.
+    match unsafe { memmap2::Mmap::map(&file) } {
+        Ok(map) => Ok(crate::smchash(&map)),
+        Err(_) => hash_reader(file),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::smchash;
+    use std::io::Cursor;
+    use std::vec::Vec;
+
+    /// Yields at most 13 bytes per read and is interrupted every other call
+    struct Choppy<'a> {
+        data: &'a [u8],
+        calls: usize,
+    }
+
+    impl Read for Choppy<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.calls += 1;
+            if self.calls.is_multiple_of(2) {
+                return Err(io::Error::from(ErrorKind::Interrupted));
+            }
+            let n = buf.len().min(self.data.len()).min(13);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_hash_reader() {
+        for &len in &[
+            0,
+            1,
+            16,
+            17,
+            127,
+            128,
+            129,
+            1000,
+            READ_BUF - 1,
+            READ_BUF,
+            READ_BUF + 129,
+        ] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 17) as u8).collect();
+            assert_eq!(
+                hash_reader(Cursor::new(&data)).unwrap(),
+                smchash(&data),
+                "len {}",
+                len
+            );
+            let choppy = Choppy {
+                data: &data,
+                calls: 0,
+            };
+            assert_eq!(hash_reader(choppy).unwrap(), smchash(&data), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_hash_reader_error() {
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::from(ErrorKind::UnexpectedEof))
+            }
+        }
+        assert_eq!(
+            hash_reader(Broken).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_hashing_writer() {
+        let data: Vec<u8> = (0..1000).map(|i| (i * 11) as u8).collect();
+        let mut writer = HashingWriter::new();
+        for chunk in data.chunks(77) {
+            writer.write_all(chunk).unwrap();
+        }
+        writer.flush().unwrap();
+        assert_eq!(writer.finish(), smchash(&data));
+
+        let mut writer = HashingWriter::with_seed(9);
+        io::copy(&mut Cursor::new(&data), &mut writer).unwrap();
+        assert_eq!(writer.finish(), crate::smchash_seeded(&data, 9));
+    }
+
+    #[test]
+    fn test_write_rand_stream() {
+        let len = READ_BUF * 2 + 13;
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        write_rand_stream(42, len, &mut a).unwrap();
+        write_rand_stream(42, len, &mut b).unwrap();
+        assert_eq!(a.len(), len);
+        assert_eq!(a, b);
+
+        let mut expected = vec![0u8; len];
+        fill_bytes(&mut 42, &mut expected);
+        assert_eq!(a, expected);
+
+        let mut empty = Vec::new();
+        write_rand_stream(42, 0, &mut empty).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_smchash_file() {
+        let path = std::env::temp_dir().join(std::format!("smchash-test-{}", std::process::id()));
+        for &len in &[0usize, 5, 129, 100_000] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 13) as u8).collect();
+            std::fs::write(&path, &data).unwrap();
+            assert_eq!(smchash_file(&path).unwrap(), smchash(&data), "len {}", len);
+        }
+        std::fs::remove_file(&path).unwrap();
+        assert!(smchash_file(&path).is_err());
+    }
+
+    #[cfg(all(feature = "mmap", target_os = "linux"))]
+    #[test]
+    fn test_smchash_file_reports_zero_length() {
+        // procfs reports a size of 0 but has contents
+        let path = "/proc/self/cmdline";
+        assert_eq!(std::fs::metadata(path).unwrap().len(), 0);
+        let data = std::fs::read(path).unwrap();
+        assert!(!data.is_empty());
+        assert_eq!(smchash_file(path).unwrap(), smchash(&data));
+    }
+}
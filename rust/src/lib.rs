@@ -48,6 +48,39 @@
 //! let hash = smchash_secret(b"data", 0, &secret);
 //! ```
 //!
+//! ## Streaming
+//!
+//! Data that arrives in chunks can be hashed with [`SmcHasher`] without
+//! concatenating it first:
+//!
+//! ```rust
+//! use smchash::{smchash, SmcHasher};
+//!
+//! let mut hasher = SmcHasher::new();
+//! hasher.update(b"Hello, ");
+//! hasher.update(b"World!");
+//! assert_eq!(hasher.finish(), smchash(b"Hello, World!"));
+//! ```
+//!
+//! ## Feature Tiers
+//!
+//! - **core** (`default-features = false`): every hash function, the PRNG,
+//!   [`SmcHasher`] and the secret helpers
+//! - **`alloc`**: adds helpers that return heap types, such as `sample_indices`
+//! - **`std`** (default): adds `SmcRandomState`, `hash_reader` and
+//!   `std::error::Error` impls
+//!
+//! ## Determinism
+//!
+//! Hash and PRNG outputs are identical on every target: little- and
+//! big-endian, 32- and 64-bit, with or without vector paths. Inputs are always
+//! read as little-endian, so a hash computed on x86_64 can be stored and
+//! compared against one computed on s390x. The only exception is the
+//! `std`-only `SmcRandomState`, which is randomly seeded per process by
+//! design. These values are pinned by hardcoded test vectors and will only
+//! change in a semver-breaking release that also bumps [`SMCHASH_VERSION`];
+//! persist that alongside stored hashes and check it with [`verify_version`].
+//!
 //! ## Performance
 //!
 //! Benchmarks on Apple M4 Max:
@@ -58,18 +91,123 @@
 //!
 //! - 128-bit MUM (Multiply-XOR-Mix) construction
 //! - 8 parallel lanes for bulk processing
-//! - Secrets are odd, prime, 32 bits set, pairwise hamming distance = 32
+//! - Secrets are odd, 32 bits set, pairwise hamming distance = 32 (only the
+//!   last default secret is prime; see [`validate_secret`])
 //!
 //! ## License
 //!
 //! MIT License - Copyright 2025 ScaleCode Solutions
 
 #![no_std]
+#![cfg_attr(
+    all(feature = "nightly-simd", not(feature = "small")),
+    feature(portable_simd)
+)]
 
+// Tests always run with an allocator, whatever the feature set
+#[cfg(any(feature = "alloc", test))]
+extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-/// Secret constants: odd, 32 bits set, pairwise hamming distance = 32, prime
+mod batch;
+mod bulk;
+mod const_hash;
+#[cfg(feature = "digest")]
+mod digest_impl;
+#[cfg(feature = "getrandom")]
+mod entropy;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod hasher;
+mod hex;
+#[cfg(feature = "std")]
+mod io;
+mod labeled;
+mod mac;
+#[cfg(feature = "bytemuck")]
+mod pod;
+pub mod primitives;
+#[cfg(test)]
+mod proptests;
+#[cfg(feature = "rand-core")]
+mod rand_impl;
+#[cfg(feature = "std")]
+mod random;
+mod rng;
+mod rolling;
+mod secret;
+#[cfg(feature = "serde")]
+mod serde_hash;
+#[cfg(feature = "alloc")]
+mod stats;
+mod streaming;
+mod tree;
+mod typed;
+#[cfg(test)]
+mod vectors;
+mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod words;
+
+pub use batch::smchash_batch;
+pub use const_hash::{smchash_array, smchash_const};
+#[cfg(feature = "digest")]
+pub use digest_impl::Smc64;
+#[cfg(feature = "getrandom")]
+pub use entropy::{random_secret, random_seed};
+pub use error::SmcError;
+pub use hasher::{
+    hash_of, SmcBuildHasher, SmcIdentityBuildHasher, SmcIdentityHasher, SmcStdHasher,
+};
+#[cfg(feature = "std")]
+pub use hasher::{smc_hashmap_with_seed, SmcHashMap, SmcHashSet, SmcRandomState};
+#[cfg(feature = "hashbrown")]
+pub use hasher::{SmcHashbrownMap, SmcHashbrownSet};
+#[cfg(feature = "indexmap")]
+pub use hasher::{SmcIndexMap, SmcIndexSet};
+pub use hex::{hash128_from_hex, hash128_to_hex, hash_from_hex, hash_to_hex};
+#[cfg(feature = "mmap")]
+pub use io::smchash_file;
+#[cfg(feature = "std")]
+pub use io::{hash_reader, write_rand_stream, HashingWriter};
+pub use labeled::{smchash_domain, smchash_labeled};
+pub use mac::{ct_eq, ct_eq128, smc_mac, smc_mac_verify};
+#[cfg(feature = "bytemuck")]
+pub use pod::smchash_pod;
+pub use primitives::{combine, combine_slice, fold128_to_64};
+#[cfg(feature = "std")]
+pub use random::{random, random_f64, random_range};
+#[cfg(feature = "std")]
+pub use rng::smc_rand_normal;
+pub use rng::{
+    choose, fill_alphanumeric, fill_bytes, random_block, shuffle, shuffle_with, smc_rand128,
+    smc_rand_bool, smc_rand_bool_p, smc_rand_f32, smc_rand_f64, smc_rand_fill_f64, smc_rand_i64,
+    smc_rand_range, smc_rand_range_inclusive, weighted_choice, SmcRng,
+};
+#[cfg(feature = "alloc")]
+pub use rng::{permutation, random_alphanumeric, sample_indices};
+pub use rolling::RollingHash;
+pub use secret::{avalanche_bias, seed_from_bytes, smc_make_secret, validate_secret, SecretError};
+#[cfg(feature = "serde")]
+pub use serde_hash::{hash_value, HashValueError};
+#[cfg(feature = "alloc")]
+pub use stats::{collision_stats, CollisionReport};
+pub use streaming::{
+    smchash_ascii_ci, smchash_chunks, smchash_iter, smchash_multi, smchash_prefix, smchash_resume,
+    SmcHasher, SmcHasherBuilder, SmcState,
+};
+pub use tree::smchash_tree;
+pub use typed::{smchash_typed, Hash128, Hash64};
+pub use verify::{smhasher_verification, verify, verify_version, SMCHASH_VERSION};
+pub use words::{smchash_u64_slice, smchash_u64x2, smchash_u64x3};
+
+use primitives::mix;
+
+/// Secret constants: odd, 32 bits set, pairwise hamming distance = 32
+/// (only the last is prime; see [`validate_secret`])
 const SMC_SECRET: [u64; 9] = [
     0x9ad1e8e2aa5a5c4b,
     0xaaaad2335647d21b,
@@ -82,15 +220,9 @@ const SMC_SECRET: [u64; 9] = [
     0x1bca69c565658bc3,
 ];
 
-/// 128-bit multiply, XOR high and low halves
-#[inline(always)]
-fn mix(a: u64, b: u64) -> u64 {
-    let r = (a as u128) * (b as u128);
-    (r as u64) ^ ((r >> 64) as u64)
-}
-
 /// Multiply-update-mix: modifies both values
-#[inline(always)]
+#[cfg_attr(feature = "small", inline)]
+#[cfg_attr(not(feature = "small"), inline(always))]
 fn mum(a: &mut u64, b: &mut u64) {
     let r = (*a as u128) * (*b as u128);
     *a = (r as u64) ^ ((r >> 64) as u64);
@@ -98,17 +230,68 @@ fn mum(a: &mut u64, b: &mut u64) {
 }
 
 /// Read little-endian u64
-#[inline(always)]
+///
+/// Input slices have arbitrary alignment, so reads must go through
+/// `from_le_bytes` on a copied array (which compiles to a single load where
+/// that is legal) rather than a pointer cast or `read_unaligned` that assumes
+/// otherwise. `test_unaligned_inputs` covers every offset.
+#[cfg_attr(feature = "small", inline)]
+#[cfg_attr(not(feature = "small"), inline(always))]
 fn read64(p: &[u8]) -> u64 {
     u64::from_le_bytes(p[..8].try_into().unwrap())
 }
 
 /// Read little-endian u32
-#[inline(always)]
+#[cfg_attr(feature = "small", inline)]
+#[cfg_attr(not(feature = "small"), inline(always))]
 fn read32(p: &[u8]) -> u32 {
     u32::from_le_bytes(p[..4].try_into().unwrap())
 }
 
+/// Fold the 0..=128 bytes `p` left after the bulk loop into `seed`
+///
+/// Shared by every long-input path. The final 16 bytes are read separately
+/// from the end of the whole input, so only the mixing happens here.
+#[cfg_attr(feature = "small", inline)]
+#[cfg_attr(not(feature = "small"), inline(always))]
+fn tail(mut p: &[u8], mut seed: u64, secret: &[u64; 9]) -> u64 {
+    #[cfg(not(feature = "small"))]
+    {
+        let mut i = p.len();
+        if i > 64 {
+            seed = mix(read64(p) ^ secret[0], read64(&p[8..]) ^ seed);
+            seed = mix(read64(&p[16..]) ^ secret[1], read64(&p[24..]) ^ seed);
+            seed = mix(read64(&p[32..]) ^ secret[2], read64(&p[40..]) ^ seed);
+            seed = mix(read64(&p[48..]) ^ secret[3], read64(&p[56..]) ^ seed);
+            p = &p[64..];
+            i -= 64;
+        }
+        if i > 32 {
+            seed = mix(read64(p) ^ secret[0], read64(&p[8..]) ^ seed);
+            seed = mix(read64(&p[16..]) ^ secret[1], read64(&p[24..]) ^ seed);
+            p = &p[32..];
+            i -= 32;
+        }
+        if i > 16 {
+            seed = mix(read64(p) ^ secret[0], read64(&p[8..]) ^ seed);
+        }
+    }
+
+    // Same steps as above: 4, 2 and 1 pairs of words, each only while more
+    // than that many bytes remain
+    #[cfg(feature = "small")]
+    for n in [4, 2, 1] {
+        if p.len() > 16 * n {
+            for (k, &s) in secret[..n].iter().enumerate() {
+                seed = mix(read64(&p[16 * k..]) ^ s, read64(&p[16 * k + 8..]) ^ seed);
+            }
+            p = &p[16 * n..];
+        }
+    }
+
+    seed
+}
+
 /// Compute smcHash of the given data.
 ///
 /// This is the primary hash function. It uses a default seed derived from
@@ -127,6 +310,7 @@ fn read32(p: &[u8]) -> u32 {
 /// assert_ne!(empty_hash, 0);
 /// ```
 #[inline]
+#[must_use]
 pub fn smchash(data: &[u8]) -> u64 {
     smchash_seeded(data, SMC_SECRET[0])
 }
@@ -136,6 +320,12 @@ pub fn smchash(data: &[u8]) -> u64 {
 /// Use this when you need different hash values for the same data,
 /// or when implementing hash tables with per-table seeds.
 ///
+/// The seed affects every output, including the hash of empty input, so
+/// empty keys in tables with different seeds do not collide. Every seed is
+/// valid, including 0: the seed is always multiplied with a secret before it
+/// meets the data, so 0 is an ordinary seed, distinct from the default used
+/// by [`smchash`](fn@smchash).
+///
 /// # Example
 ///
 /// ```rust
@@ -145,7 +335,112 @@ pub fn smchash(data: &[u8]) -> u64 {
 /// let hash2 = smchash_seeded(b"data", 2);
 /// assert_ne!(hash1, hash2); // Different seeds produce different hashes
 /// ```
-pub fn smchash_seeded(data: &[u8], mut seed: u64) -> u64 {
+#[inline]
+#[must_use]
+pub fn smchash_seeded(data: &[u8], seed: u64) -> u64 {
+    let (a, b) = smchash_state(data, seed);
+    mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ (data.len() as u64))
+}
+
+/// Compute smcHash of a string's UTF-8 bytes.
+///
+/// Same as `smchash(s.as_bytes())`, and usable in `const` contexts. Like
+/// [`smchash_const`] it reads one byte at a time, so at runtime it is several
+/// times slower than [`smchash`](fn@smchash) on long strings; prefer
+/// `smchash(s.as_bytes())` in hot paths.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash, smchash_str};
+///
+/// const USER: u64 = smchash_str("user");
+/// assert_eq!(USER, smchash(b"user"));
+/// assert_eq!(smchash_str("x"), smchash(b"x"));
+/// ```
+#[must_use]
+pub const fn smchash_str(s: &str) -> u64 {
+    const_hash::smchash_seeded_const(s.as_bytes(), SMC_SECRET[0])
+}
+
+/// Compute smcHash of a string's UTF-8 bytes with a custom seed.
+///
+/// Same as `smchash_seeded(s.as_bytes(), seed)`, and usable in `const`
+/// contexts with the same runtime cost as [`smchash_str`].
+#[must_use]
+pub const fn smchash_str_seeded(s: &str, seed: u64) -> u64 {
+    const_hash::smchash_seeded_const(s.as_bytes(), seed)
+}
+
+/// Hash only the first `init_len` bytes of `buf`.
+///
+/// For buffers that are filled in part, e.g. by a `read` into a reused
+/// buffer: hashing the whole buffer would include stale or never-written
+/// bytes past the filled prefix. Same as `smchash_seeded(&buf[..init_len], seed)`,
+/// with the length check spelled out at the call site.
+///
+/// # Panics
+///
+/// Panics if `init_len > buf.len()`.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_init_prefix, smchash_seeded};
+///
+/// let mut buf = [0u8; 4096];
+/// buf[..5].copy_from_slice(b"hello");
+/// let filled = 5; // e.g. returned by `Read::read`
+/// assert_eq!(smchash_init_prefix(&buf, filled, 0), smchash_seeded(b"hello", 0));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash_init_prefix(buf: &[u8], init_len: usize, seed: u64) -> u64 {
+    assert!(
+        init_len <= buf.len(),
+        "smchash_init_prefix: init_len {} exceeds buffer length {}",
+        init_len,
+        buf.len()
+    );
+    smchash_seeded(&buf[..init_len], seed)
+}
+
+/// Hash the window `data[start..end]`.
+///
+/// Same as `smchash_seeded(&data[start..end], seed)`, for code that hashes
+/// many overlapping windows of one buffer. The bounds are checked up front
+/// and a bad range panics with both indices and the buffer length in the
+/// message.
+///
+/// # Panics
+///
+/// Panics if `start > end` or `end > data.len()`.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_range, smchash_seeded};
+///
+/// let buf = b"the quick brown fox";
+/// assert_eq!(smchash_range(buf, 4, 9, 0), smchash_seeded(b"quick", 0));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash_range(data: &[u8], start: usize, end: usize, seed: u64) -> u64 {
+    assert!(
+        start <= end && end <= data.len(),
+        "smchash_range: range {}..{} out of bounds for length {}",
+        start,
+        end,
+        data.len()
+    );
+    smchash_seeded(&data[start..end], seed)
+}
+
+/// Core of [`smchash_seeded`]: the 128-bit MUM state before the final fold
+#[cfg_attr(feature = "small", inline)]
+#[cfg_attr(not(feature = "small"), inline(always))]
+fn smchash_state(data: &[u8], mut seed: u64) -> (u64, u64) {
     let mut p = data;
     let len = data.len();
     let a: u64;
@@ -163,6 +458,7 @@ pub fn smchash_seeded(data: &[u8], mut seed: u64) -> u64 {
                 b = read32(&p[len - 4..]) as u64;
             }
         } else if len > 0 {
+            // Lossless for each length (the length itself is in `seed`)
             a = ((p[0] as u64) << 56) | ((p[len >> 1] as u64) << 32) | (p[len - 1] as u64);
             b = 0;
         } else {
@@ -173,7 +469,7 @@ pub fn smchash_seeded(data: &[u8], mut seed: u64) -> u64 {
         let mut a = a ^ SMC_SECRET[1];
         let mut b = b ^ seed;
         mum(&mut a, &mut b);
-        return mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ (len as u64));
+        return (a, b);
     }
 
     seed ^= mix(seed ^ SMC_SECRET[2], SMC_SECRET[1]);
@@ -181,49 +477,18 @@ pub fn smchash_seeded(data: &[u8], mut seed: u64) -> u64 {
 
     // Bulk: 8 lanes = 128 bytes = 2 cache lines
     if len > 128 {
-        let mut see1 = seed;
-        let mut see2 = seed;
-        let mut see3 = seed;
-        let mut see4 = seed;
-        let mut see5 = seed;
-        let mut see6 = seed;
-        let mut see7 = seed;
+        let mut lanes = [seed; 8];
 
         while i > 128 {
-            seed = mix(read64(p) ^ SMC_SECRET[0], read64(&p[8..]) ^ seed);
-            see1 = mix(read64(&p[16..]) ^ SMC_SECRET[1], read64(&p[24..]) ^ see1);
-            see2 = mix(read64(&p[32..]) ^ SMC_SECRET[2], read64(&p[40..]) ^ see2);
-            see3 = mix(read64(&p[48..]) ^ SMC_SECRET[3], read64(&p[56..]) ^ see3);
-            see4 = mix(read64(&p[64..]) ^ SMC_SECRET[4], read64(&p[72..]) ^ see4);
-            see5 = mix(read64(&p[80..]) ^ SMC_SECRET[5], read64(&p[88..]) ^ see5);
-            see6 = mix(read64(&p[96..]) ^ SMC_SECRET[6], read64(&p[104..]) ^ see6);
-            see7 = mix(read64(&p[112..]) ^ SMC_SECRET[7], read64(&p[120..]) ^ see7);
+            bulk::accumulate(&mut lanes, p, &SMC_SECRET);
             p = &p[128..];
             i -= 128;
         }
 
-        seed ^= see1 ^ see4 ^ see5;
-        see2 ^= see3 ^ see6 ^ see7;
-        seed ^= see2;
+        seed = bulk::fold(&lanes);
     }
 
-    if i > 64 {
-        seed = mix(read64(p) ^ SMC_SECRET[0], read64(&p[8..]) ^ seed);
-        seed = mix(read64(&p[16..]) ^ SMC_SECRET[1], read64(&p[24..]) ^ seed);
-        seed = mix(read64(&p[32..]) ^ SMC_SECRET[2], read64(&p[40..]) ^ seed);
-        seed = mix(read64(&p[48..]) ^ SMC_SECRET[3], read64(&p[56..]) ^ seed);
-        p = &p[64..];
-        i -= 64;
-    }
-    if i > 32 {
-        seed = mix(read64(p) ^ SMC_SECRET[0], read64(&p[8..]) ^ seed);
-        seed = mix(read64(&p[16..]) ^ SMC_SECRET[1], read64(&p[24..]) ^ seed);
-        p = &p[32..];
-        i -= 32;
-    }
-    if i > 16 {
-        seed = mix(read64(p) ^ SMC_SECRET[0], read64(&p[8..]) ^ seed);
-    }
+    seed = tail(&p[..i], seed, &SMC_SECRET);
 
     a = read64(&data[len - 16..]) ^ (len as u64);
     b = read64(&data[len - 8..]);
@@ -231,7 +496,7 @@ pub fn smchash_seeded(data: &[u8], mut seed: u64) -> u64 {
     let mut a = a ^ SMC_SECRET[1];
     let mut b = b ^ seed;
     mum(&mut a, &mut b);
-    mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ (len as u64))
+    (a, b)
 }
 
 /// Compute smcHash with custom secrets.
@@ -269,8 +534,27 @@ pub fn smchash_seeded(data: &[u8], mut seed: u64) -> u64 {
 /// - Each pair differs by exactly 32 bits (hamming distance)
 /// - Each is prime
 ///
-/// The C implementation includes `smc_make_secret()` to generate valid secrets.
-pub fn smchash_secret(data: &[u8], mut seed: u64, secret: &[u64; 9]) -> u64 {
+/// Use [`smc_make_secret`] to generate valid secrets and [`validate_secret`]
+/// to check secrets loaded from elsewhere.
+///
+/// # Panics
+///
+/// In debug builds, panics if `secret` fails [`validate_secret`]. The
+/// crate's built-in default secret predates that check and is exempt. The
+/// check is compiled out of release builds.
+#[inline]
+#[must_use]
+pub fn smchash_secret(data: &[u8], seed: u64, secret: &[u64; 9]) -> u64 {
+    debug_assert!(
+        *secret == SMC_SECRET || validate_secret(secret).is_ok(),
+        "smchash_secret: secret fails validate_secret"
+    );
+    smchash_secret_unchecked(data, seed, secret)
+}
+
+/// [`smchash_secret`] without the debug-build secret check, for callers that
+/// deliberately hash with arbitrary secrets or have already checked them.
+pub(crate) fn smchash_secret_unchecked(data: &[u8], mut seed: u64, secret: &[u64; 9]) -> u64 {
     let mut p = data;
     let len = data.len();
     let a: u64;
@@ -305,49 +589,18 @@ pub fn smchash_secret(data: &[u8], mut seed: u64, secret: &[u64; 9]) -> u64 {
     let mut i = len;
 
     if len > 128 {
-        let mut see1 = seed;
-        let mut see2 = seed;
-        let mut see3 = seed;
-        let mut see4 = seed;
-        let mut see5 = seed;
-        let mut see6 = seed;
-        let mut see7 = seed;
+        let mut lanes = [seed; 8];
 
         while i > 128 {
-            seed = mix(read64(p) ^ secret[0], read64(&p[8..]) ^ seed);
-            see1 = mix(read64(&p[16..]) ^ secret[1], read64(&p[24..]) ^ see1);
-            see2 = mix(read64(&p[32..]) ^ secret[2], read64(&p[40..]) ^ see2);
-            see3 = mix(read64(&p[48..]) ^ secret[3], read64(&p[56..]) ^ see3);
-            see4 = mix(read64(&p[64..]) ^ secret[4], read64(&p[72..]) ^ see4);
-            see5 = mix(read64(&p[80..]) ^ secret[5], read64(&p[88..]) ^ see5);
-            see6 = mix(read64(&p[96..]) ^ secret[6], read64(&p[104..]) ^ see6);
-            see7 = mix(read64(&p[112..]) ^ secret[7], read64(&p[120..]) ^ see7);
+            bulk::accumulate(&mut lanes, p, secret);
             p = &p[128..];
             i -= 128;
         }
 
-        seed ^= see1 ^ see4 ^ see5;
-        see2 ^= see3 ^ see6 ^ see7;
-        seed ^= see2;
+        seed = bulk::fold(&lanes);
     }
 
-    if i > 64 {
-        seed = mix(read64(p) ^ secret[0], read64(&p[8..]) ^ seed);
-        seed = mix(read64(&p[16..]) ^ secret[1], read64(&p[24..]) ^ seed);
-        seed = mix(read64(&p[32..]) ^ secret[2], read64(&p[40..]) ^ seed);
-        seed = mix(read64(&p[48..]) ^ secret[3], read64(&p[56..]) ^ seed);
-        p = &p[64..];
-        i -= 64;
-    }
-    if i > 32 {
-        seed = mix(read64(p) ^ secret[0], read64(&p[8..]) ^ seed);
-        seed = mix(read64(&p[16..]) ^ secret[1], read64(&p[24..]) ^ seed);
-        p = &p[32..];
-        i -= 32;
-    }
-    if i > 16 {
-        seed = mix(read64(p) ^ secret[0], read64(&p[8..]) ^ seed);
-    }
+    seed = tail(&p[..i], seed, secret);
 
     a = read64(&data[len - 16..]) ^ (len as u64);
     b = read64(&data[len - 8..]);
@@ -358,6 +611,182 @@ pub fn smchash_secret(data: &[u8], mut seed: u64, secret: &[u64; 9]) -> u64 {
     mix(a ^ secret[8], b ^ secret[1] ^ (len as u64))
 }
 
+/// Compute a 128-bit smcHash of the given data.
+///
+/// Intended for deduplication and content addressing, where 64 bits leave too
+/// little headroom against collisions. The low 64 bits are exactly
+/// [`smchash`](fn@smchash), so callers that need both widths get them from one call.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash, smchash128};
+///
+/// let hash = smchash128(b"Hello, World!");
+/// assert_eq!(hash as u64, smchash(b"Hello, World!"));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash128(data: &[u8]) -> u128 {
+    smchash128_seeded(data, SMC_SECRET[0])
+}
+
+/// Compute a 128-bit smcHash with a custom seed.
+///
+/// The low 64 bits equal [`smchash_seeded`] with the same seed. The high 64
+/// bits fold the same MUM state with a different pair of secrets.
+///
+/// Both halves are independently well distributed: flipping any input bit
+/// flips about half of the bits in each half on its own. One call can
+/// therefore serve two purposes, e.g. the top `k` bits of the high half
+/// select one of `2^k` shards while the low half drives probing within the
+/// shard.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smchash128_seeded;
+///
+/// let h = smchash128_seeded(b"user:42", 7);
+/// let shard = (h >> 120) as usize; // one of 256 shards
+/// let probe = h as u64;
+/// # let _ = (shard, probe);
+/// ```
+#[must_use]
+pub fn smchash128_seeded(data: &[u8], seed: u64) -> u128 {
+    let len = data.len() as u64;
+    let (a, b) = smchash_state(data, seed);
+    let lo = mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ len);
+    let hi = mix(a ^ SMC_SECRET[2], b ^ SMC_SECRET[7] ^ len);
+    ((hi as u128) << 64) | (lo as u128)
+}
+
+/// Compute a 128-bit smcHash with a custom seed.
+///
+/// Same function as [`smchash128_seeded`], named after [`smchash_seeded`]:
+/// the low 64 bits equal `smchash_seeded(data, seed)`, and the high and low
+/// halves are each well distributed on their own, so they can serve as
+/// independent bit ranges (e.g. shard selection and probing).
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_seeded, smchash_seeded128};
+///
+/// let h = smchash_seeded128(b"user:42", 7);
+/// assert_eq!(h as u64, smchash_seeded(b"user:42", 7));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash_seeded128(data: &[u8], seed: u64) -> u128 {
+    smchash128_seeded(data, seed)
+}
+
+/// Compute smcHash with a 128-bit seed.
+///
+/// For keying beyond the 64 bits of [`smchash_seeded`] without supplying a
+/// full secret. The high half of the seed is folded both into the initial
+/// seed and into the final fold, so all 128 bits affect the output. With a
+/// zero high half this is exactly `smchash_seeded(data, seed as u64)`.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_seed128, smchash_seeded};
+///
+/// assert_eq!(smchash_seed128(b"data", 7), smchash_seeded(b"data", 7));
+/// assert_ne!(smchash_seed128(b"data", 7 | 1 << 64), smchash_seeded(b"data", 7));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash_seed128(data: &[u8], seed: u128) -> u64 {
+    let lo = seed as u64;
+    let hi = (seed >> 64) as u64;
+    // mix(0, _) == 0, so a zero high half leaves both the seed and the fold unchanged
+    let (a, b) = smchash_state(data, lo ^ mix(hi, SMC_SECRET[5]));
+    mix(
+        a ^ SMC_SECRET[8] ^ hi,
+        b ^ SMC_SECRET[1] ^ (data.len() as u64),
+    )
+}
+
+/// Compute smcHash with two 64-bit seeds.
+///
+/// For migrating from hashers whose API takes a pair of seeds. `seed0` keys
+/// the primary state exactly as in [`smchash_seeded`]; `seed1` is a
+/// secondary key folded into both the initial state and the final fold.
+/// With `seed1 == 0` this is exactly `smchash_seeded(data, seed0)`.
+///
+/// This is a separate entry point from [`smchash_seed128`], not a separate
+/// hash family: it equals `smchash_seed128(data, (seed1 as u128) << 64 | seed0 as u128)`,
+/// so code using either form gets the same values.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_seeded, smchash_seeds};
+///
+/// assert_eq!(smchash_seeds(b"data", 7, 0), smchash_seeded(b"data", 7));
+/// assert_ne!(smchash_seeds(b"data", 7, 1), smchash_seeds(b"data", 7, 0));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash_seeds(data: &[u8], seed0: u64, seed1: u64) -> u64 {
+    smchash_seed128(data, ((seed1 as u128) << 64) | seed0 as u128)
+}
+
+/// Return the raw 128-bit state of [`smchash_seeded`] before the final fold.
+///
+/// For collision research: `(a, b)` is the state right after the last MUM
+/// step, and the 64-bit hash is always
+/// `mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ len)`, with `mix` from
+/// [`primitives`]. Two inputs of equal length with the same raw state always
+/// collide; comparing raw states shows whether a collision happened before or
+/// in the final fold.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_raw128, smchash_seeded};
+/// use smchash::primitives::mix;
+///
+/// let (a, b) = smchash_raw128(b"data", 7);
+/// let h = mix(a ^ 0x1bca69c565658bc3, b ^ 0xaaaad2335647d21b ^ 4);
+/// assert_eq!(h, smchash_seeded(b"data", 7));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash_raw128(data: &[u8], seed: u64) -> (u64, u64) {
+    smchash_state(data, seed)
+}
+
+/// Compute a 32-bit smcHash of the given data.
+///
+/// For 32-bit hash table slots. Rather than truncating [`smchash`](fn@smchash), the
+/// 64-bit hash goes through one more [`mix`] with a secret and both halves are
+/// folded together, so every output bit depends on every input bit.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smchash32;
+///
+/// let slot = smchash32(b"key") as usize % 1024;
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash32(data: &[u8]) -> u32 {
+    smchash32_seeded(data, SMC_SECRET[0])
+}
+
+/// Compute a 32-bit smcHash with a custom seed.
+#[inline]
+#[must_use]
+pub fn smchash32_seeded(data: &[u8], seed: u64) -> u32 {
+    let h = mix(smchash_seeded(data, seed) ^ SMC_SECRET[3], SMC_SECRET[6]);
+    (h ^ (h >> 32)) as u32
+}
+
 /// Pseudo-random number generator.
 ///
 /// A fast PRNG that passes both BigCrush (TestU01) and PractRand statistical tests.
@@ -387,6 +816,7 @@ pub fn smchash_secret(data: &[u8], mut seed: u64, secret: &[u64; 9]) -> u64 {
 /// - Passes PractRand to 256MB+
 /// - Equivalent quality to wyrand (rapidhash PRNG)
 #[inline]
+#[must_use]
 pub fn smc_rand(seed: &mut u64) -> u64 {
     *seed = seed.wrapping_add(SMC_SECRET[0]);
     mix(*seed, *seed ^ SMC_SECRET[1])
@@ -410,12 +840,62 @@ mod tests {
         assert_eq!(smchash_seeded(data, 12345), expected);
     }
 
+    #[test]
+    fn test_hash_str() {
+        assert_eq!(smchash_str("x"), smchash(b"x"));
+        assert_eq!(smchash_str(""), smchash(b""));
+        assert_eq!(smchash_str("héllo"), smchash("héllo".as_bytes()));
+        assert_eq!(smchash_str_seeded("x", 9), smchash_seeded(b"x", 9));
+        const X: u64 = smchash!("x");
+        assert_eq!(smchash_str("x"), X);
+        const SEEDED: u64 = smchash_str_seeded("héllo", 9);
+        assert_eq!(SEEDED, smchash_seeded("héllo".as_bytes(), 9));
+    }
+
     #[test]
     fn test_hash_empty() {
         let result = smchash(b"");
         assert_ne!(result, 0);
     }
 
+    #[test]
+    fn test_empty_seed_propagation() {
+        // The seed reaches the output even with no data
+        assert_ne!(smchash_seeded(b"", 1), smchash_seeded(b"", 2));
+        let mut seen: alloc::vec::Vec<u64> = (0..1000).map(|s| smchash_seeded(b"", s)).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), 1000);
+    }
+
+    #[test]
+    fn test_seed_zero() {
+        let mut rng = 88u64;
+        let mut buf = [0u8; 300];
+        fill_bytes(&mut rng, &mut buf);
+        let mut differing_bits = 0u32;
+        let lens = [0usize, 1, 3, 4, 8, 16, 17, 32, 64, 128, 129, 300];
+        for &len in &lens {
+            let data = &buf[..len];
+            let zero = smchash_seeded(data, 0);
+            assert_ne!(zero, smchash(data), "len {}", len);
+            for seed in [1, 2, u64::MAX, 1 << 63] {
+                assert_ne!(zero, smchash_seeded(data, seed), "len {}", len);
+            }
+            differing_bits += (zero ^ smchash(data)).count_ones();
+        }
+        let mean = differing_bits as f64 / lens.len() as f64;
+        assert!((24.0..40.0).contains(&mean), "{}", mean);
+
+        // Seed 0 still separates inputs like any other seed
+        let mut seen: alloc::vec::Vec<u64> = (0..=buf.len())
+            .map(|len| smchash_seeded(&buf[..len], 0))
+            .collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), buf.len() + 1);
+    }
+
     #[test]
     fn test_rand() {
         let mut seed = 42u64;
@@ -430,13 +910,375 @@ mod tests {
     #[test]
     fn test_different_lengths() {
         let lengths = [1, 2, 3, 4, 5, 7, 8, 15, 16, 17, 31, 32, 33, 63, 64, 65, 127, 128, 129];
-        let mut hashes = std::vec::Vec::new();
+        let mut hashes = alloc::vec::Vec::new();
         
         for &len in &lengths {
-            let data: std::vec::Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let data: alloc::vec::Vec<u8> = (0..len).map(|i| i as u8).collect();
             let h = smchash(&data);
             assert!(!hashes.contains(&h), "Collision at length {}", len);
             hashes.push(h);
         }
     }
+
+    #[test]
+    fn test_hash128() {
+        let d16: alloc::vec::Vec<u8> = (0..16).collect();
+        let d200: alloc::vec::Vec<u8> = (0..200).map(|i| i as u8).collect();
+
+        assert_eq!(smchash128(b""), 0xfaf88c3b2cd2548476eee9b64c443120);
+        assert_eq!(smchash128(&d16), 0x6aae9f86560dc0c6a321ff5b51af5140);
+        assert_eq!(smchash128(&d200), 0x5ea2dc352ff1759184e1acd049ee28b8);
+
+        for data in [&b""[..], &d16, &d200] {
+            assert_eq!(smchash128(data) as u64, smchash(data));
+            assert_eq!(smchash128_seeded(data, 99) as u64, smchash_seeded(data, 99));
+        }
+    }
+
+    #[test]
+    fn test_seeded128() {
+        assert_eq!(
+            smchash_seeded128(b"", 7),
+            0x4afc3065f46f6eb1a19bd8d2c7b17ce2
+        );
+        assert_eq!(
+            smchash_seeded128(b"user:42", 7),
+            0x4378c120f2fe7450b5554d2280df0309
+        );
+        let d200: alloc::vec::Vec<u8> = (0..200).map(|i| i as u8).collect();
+        for data in [&b""[..], b"user:42", &d200] {
+            assert_eq!(smchash_seeded128(data, 7), smchash128_seeded(data, 7));
+            assert_eq!(smchash_seeded128(data, 7) as u64, smchash_seeded(data, 7));
+        }
+    }
+
+    #[test]
+    fn test_unaligned_inputs() {
+        // Hashing must not depend on where the input starts relative to word
+        // alignment, which would otherwise trap on strict-alignment targets
+        #[repr(align(16))]
+        struct Aligned([u8; 1100]);
+
+        let mut buf = Aligned([0; 1100]);
+        for (i, b) in buf.0.iter_mut().enumerate() {
+            *b = (i * 131 + 17) as u8;
+        }
+
+        for offset in 0..8 {
+            for len in [0, 3, 4, 7, 8, 15, 16, 17, 33, 65, 128, 129, 300, 1000] {
+                let data = &buf.0[offset..offset + len];
+                assert_eq!(data.as_ptr() as usize % 8, offset);
+                let isolated: alloc::vec::Vec<u8> = data.to_vec();
+
+                assert_eq!(
+                    smchash(data),
+                    smchash(&isolated),
+                    "offset {} len {}",
+                    offset,
+                    len
+                );
+                assert_eq!(smchash_seeded(data, 5), smchash_seeded(&isolated, 5));
+                assert_eq!(
+                    smchash_secret(data, 5, &SMC_SECRET),
+                    smchash_secret(&isolated, 5, &SMC_SECRET)
+                );
+                assert_eq!(smchash128(data), smchash128(&isolated));
+                assert_eq!(smchash_const(data), smchash(&isolated));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash32() {
+        assert_ne!(smchash32(b"a"), smchash32(b"b"));
+        assert_ne!(smchash32_seeded(b"a", 1), smchash32_seeded(b"a", 2));
+        assert_eq!(smchash32(b"data"), smchash32_seeded(b"data", SMC_SECRET[0]));
+
+        // Flipping any single input bit flips about half of the 32 output bits
+        let mut seed = 21u64;
+        for len in [4usize, 8, 16, 40] {
+            let mut total = 0u32;
+            let mut count = 0u32;
+            for _ in 0..200 {
+                let mut data = [0u8; 40];
+                fill_bytes(&mut seed, &mut data[..len]);
+                let h = smchash32(&data[..len]);
+                for bit in 0..len * 8 {
+                    data[bit / 8] ^= 1 << (bit % 8);
+                    total += (h ^ smchash32(&data[..len])).count_ones();
+                    count += 1;
+                    data[bit / 8] ^= 1 << (bit % 8);
+                }
+            }
+            let mean = total as f64 / count as f64;
+            assert!((15.5..16.5).contains(&mean), "len {} mean {}", len, mean);
+        }
+    }
+
+    #[test]
+    fn test_raw128() {
+        let data: alloc::vec::Vec<u8> = (0..300).map(|i| (i * 5) as u8).collect();
+        for len in [0, 3, 8, 16, 17, 100, 128, 129, 300] {
+            let (a, b) = smchash_raw128(&data[..len], 11);
+            assert_eq!(
+                mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ len as u64),
+                smchash_seeded(&data[..len], 11)
+            );
+        }
+    }
+
+    #[test]
+    fn test_seed128() {
+        let data: alloc::vec::Vec<u8> = (0..300).map(|i| (i * 5) as u8).collect();
+        for len in [0, 3, 8, 16, 17, 100, 128, 129, 300] {
+            let d = &data[..len];
+            assert_eq!(smchash_seed128(d, 11), smchash_seeded(d, 11));
+            assert_eq!(
+                smchash_seed128(d, u64::MAX as u128),
+                smchash_seeded(d, u64::MAX)
+            );
+
+            let base = smchash_seed128(d, 11);
+            for bit in 64..128 {
+                assert_ne!(smchash_seed128(d, 11 | 1 << bit), base, "bit {}", bit);
+            }
+            assert_ne!(
+                smchash_seed128(d, 11 | 1 << 64),
+                smchash_seed128(d, 11 | 2 << 64)
+            );
+        }
+    }
+
+    #[test]
+    fn test_seeds() {
+        let mut buf = [0u8; 300];
+        fill_bytes(&mut 94, &mut buf);
+        for len in [0usize, 5, 16, 17, 100, 129, 300] {
+            let d = &buf[..len];
+            assert_eq!(smchash_seeds(d, 11, 0), smchash_seeded(d, 11));
+            assert_eq!(smchash_seeds(d, 11, 22), smchash_seed128(d, 22 << 64 | 11));
+
+            let base = smchash_seeds(d, 11, 22);
+            assert_ne!(base, smchash_seeds(d, 12, 22), "len {}", len);
+            assert_ne!(base, smchash_seeds(d, 11, 23), "len {}", len);
+            assert_ne!(base, smchash_seeds(d, 22, 11), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_init_prefix() {
+        let mut buf = [0u8; 300];
+        fill_bytes(&mut 84, &mut buf);
+        for len in 0..=buf.len() {
+            assert_eq!(
+                smchash_init_prefix(&buf, len, 9),
+                smchash_seeded(&buf[..len], 9)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds buffer length")]
+    fn test_init_prefix_out_of_bounds() {
+        let _ = smchash_init_prefix(&[0u8; 8], 9, 0);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut buf = [0u8; 200];
+        fill_bytes(&mut 93, &mut buf);
+        for start in (0..=buf.len()).step_by(7) {
+            for end in (start..=buf.len()).step_by(11) {
+                assert_eq!(
+                    smchash_range(&buf, start, end, 3),
+                    smchash_seeded(&buf[start..end], 3)
+                );
+            }
+        }
+        assert_eq!(smchash_range(&buf, 200, 200, 3), smchash_seeded(b"", 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "range 5..4 out of bounds")]
+    fn test_range_reversed() {
+        let _ = smchash_range(&[0u8; 8], 5, 4, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 0..9 out of bounds for length 8")]
+    fn test_range_past_end() {
+        let _ = smchash_range(&[0u8; 8], 0, 9, 0);
+    }
+
+    #[test]
+    fn test_secret_debug_check_accepts_valid() {
+        let secret = smc_make_secret(0);
+        assert_eq!(validate_secret(&secret), Ok(()));
+        let _ = smchash_secret(b"data", 0, &secret);
+        let _ = smchash_secret(b"data", 0, &SMC_SECRET);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "fails validate_secret")]
+    fn test_secret_debug_check_rejects_invalid() {
+        let mut secret = smc_make_secret(0);
+        secret[4] ^= 1;
+        let _ = smchash_secret(b"data", 0, &secret);
+    }
+
+    #[test]
+    fn test_short_lengths_match_reference() {
+        // Every branch of the len <= 16 dispatch against the byte-wise const path
+        let mut rng = 91u64;
+        let mut key = [0u8; 16];
+        for len in 1..=16 {
+            for _ in 0..200 {
+                fill_bytes(&mut rng, &mut key[..len]);
+                let seed = smc_rand(&mut rng);
+                assert_eq!(
+                    smchash_seeded(&key[..len], seed),
+                    const_hash::smchash_seeded_const(&key[..len], seed),
+                    "len {}",
+                    len
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_1_to_3_bytes_exhaustive() {
+        // The tiny-input packing is injective per length and the length is
+        // folded into the seed, so all ~16.8M keys should hash apart; a random
+        // 64-bit function collides here with probability below 1e-5
+        let mut hashes = alloc::vec::Vec::with_capacity(256 + (1 << 16) + (1 << 24));
+        for x in 0..1u32 << 24 {
+            let k = x.to_le_bytes();
+            hashes.push(smchash(&k[..3]));
+            if x < 1 << 16 {
+                hashes.push(smchash(&k[..2]));
+            }
+            if x < 1 << 8 {
+                hashes.push(smchash(&k[..1]));
+            }
+        }
+        let n = hashes.len();
+        hashes.sort_unstable();
+        hashes.dedup();
+        assert_eq!(hashes.len(), n);
+    }
+
+    #[test]
+    fn test_tail_overlap_differences() {
+        // Above 16 bytes the final step rereads the last 16 bytes, most of
+        // which the block and tail loops have already mixed in. Inputs that
+        // differ only there must still hash apart: the difference reaches the
+        // state twice, and the two must not cancel
+        let mut seed = 81u64;
+        let mut base = [0u8; 272];
+        let mut hashes = alloc::vec::Vec::new();
+        for len in 17..=base.len() {
+            fill_bytes(&mut seed, &mut base[..len]);
+            hashes.clear();
+            hashes.push(smchash(&base[..len]));
+
+            let mut key = base;
+            for pos in len - 16..len {
+                for delta in [1u8, 2, 4, 8, 16, 32, 64, 128, 0xff] {
+                    key[pos] ^= delta;
+                    hashes.push(smchash(&key[..len]));
+                    key[pos] ^= delta;
+                }
+            }
+            for _ in 0..16 {
+                let mut delta = [0u8; 16];
+                fill_bytes(&mut seed, &mut delta);
+                for (k, d) in key[len - 16..len].iter_mut().zip(delta) {
+                    *k ^= d;
+                }
+                hashes.push(smchash(&key[..len]));
+            }
+
+            let n = hashes.len();
+            hashes.sort_unstable();
+            hashes.dedup();
+            assert_eq!(hashes.len(), n, "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_17_to_32_bytes() {
+        // This class runs a single mix over the first 16 bytes plus the
+        // overlapping tail read, so check it on its own
+        let mut hashes = alloc::vec::Vec::new();
+        for len in 17..=32usize {
+            for i in 0..20_000u64 {
+                let mut key = [b'k'; 32];
+                key[..8].copy_from_slice(&i.to_le_bytes());
+                key[len - 8..len].copy_from_slice(&(i / 3).to_le_bytes());
+                hashes.push(smchash(&key[..len]));
+            }
+        }
+        let n = hashes.len();
+        hashes.sort_unstable();
+        hashes.dedup();
+        assert_eq!(hashes.len(), n);
+
+        let mut seed = 17u64;
+        let samples = 1000;
+        let mut key = [0u8; 32];
+        for len in 17..=32usize {
+            for bit in 0..len * 8 {
+                let (mut total, mut flips) = (0u32, [0u32; 64]);
+                for _ in 0..samples {
+                    fill_bytes(&mut seed, &mut key[..len]);
+                    let h = smchash(&key[..len]);
+                    key[bit / 8] ^= 1 << (bit % 8);
+                    let diff = h ^ smchash(&key[..len]);
+                    total += diff.count_ones();
+                    for (out, count) in flips.iter_mut().enumerate() {
+                        *count += (diff >> out) as u32 & 1;
+                    }
+                }
+                let mean = total as f64 / samples as f64;
+                assert!((31.0..33.0).contains(&mean), "len {} bit {}", len, bit);
+                for &count in &flips {
+                    let p = count as f64 / samples as f64;
+                    assert!((0.4..0.6).contains(&p), "len {} bit {}: {}", len, bit, p);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash128_halves_avalanche() {
+        // Each half on its own flips about 32 of 64 bits per input bit flip
+        let mut seed = 31u64;
+        for len in [8usize, 24, 200] {
+            let (mut lo_total, mut hi_total, mut count) = (0u64, 0u64, 0u64);
+            let mut data = [0u8; 200];
+            for _ in 0..50 {
+                fill_bytes(&mut seed, &mut data[..len]);
+                let h = smchash128_seeded(&data[..len], 99);
+                for bit in 0..len * 8 {
+                    data[bit / 8] ^= 1 << (bit % 8);
+                    let d = h ^ smchash128_seeded(&data[..len], 99);
+                    data[bit / 8] ^= 1 << (bit % 8);
+                    lo_total += (d as u64).count_ones() as u64;
+                    hi_total += ((d >> 64) as u64).count_ones() as u64;
+                    count += 1;
+                }
+            }
+            for (half, total) in [("low", lo_total), ("high", hi_total)] {
+                let mean = total as f64 / count as f64;
+                assert!(
+                    (31.5..32.5).contains(&mean),
+                    "{} len {} mean {}",
+                    half,
+                    len,
+                    mean
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,140 @@
+//! Keyed message authentication for non-adversarial settings.
+
+use crate::smchash_secret;
+
+/// Compute a 64-bit authentication tag for `data` under `key`.
+///
+/// This is [`smchash_secret`] with `key` as the secret and a zero seed. Keys
+/// should satisfy the usual secret properties; generate them with
+/// [`smc_make_secret`](crate::smc_make_secret).
+///
+/// **This is not a cryptographic MAC.** smcHash is a fast non-cryptographic
+/// hash; an attacker who can observe tags or query a verifier may be able to
+/// recover the key or forge tags. Use it to detect accidental corruption or
+/// tampering by well-behaved internal components, and use HMAC or a real MAC
+/// (e.g. Poly1305, BLAKE3 keyed mode) anywhere security matters.
+#[inline]
+#[must_use]
+pub fn smc_mac(data: &[u8], key: &[u64; 9]) -> u64 {
+    smchash_secret(data, 0, key)
+}
+
+/// Check a tag produced by [`smc_mac`], comparing in constant time.
+///
+/// The comparison does not exit early on the first differing bit, so timing
+/// does not reveal how much of the tag matched. Hashing `data` itself is not
+/// constant time with respect to its length.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smc_mac, smc_mac_verify};
+///
+/// let key = [
+///     0xf03aa5f0392b74c3, 0xa6ca3378b4cc6c35, 0xe1274b4b960f1ec5,
+///     0xe46cac0f4d8d5933, 0xd4b8478d3cd42bc9, 0xa3d24bc96335711b,
+///     0xb85a9a1e5578178d, 0x636596992ee8b499, 0x635cd16a99d18d4b,
+/// ];
+/// let tag = smc_mac(b"payload", &key);
+/// assert!(smc_mac_verify(b"payload", &key, tag));
+/// assert!(!smc_mac_verify(b"paylaod", &key, tag));
+/// ```
+#[must_use]
+pub fn smc_mac_verify(data: &[u8], key: &[u64; 9], tag: u64) -> bool {
+    ct_eq(smc_mac(data, key), tag)
+}
+
+/// Compare two 64-bit values without branching on their contents.
+///
+/// Use this when comparing tags or hash-derived keys where the time taken
+/// should not depend on how many bits match, e.g. on shared hosts. The
+/// differences are accumulated with bitwise operations and the result is
+/// read back through a volatile load, so the optimizer cannot turn it back
+/// into an early-exit comparison.
+///
+/// This is best effort: Rust makes no guarantee about the timing of the
+/// generated code, and the surrounding program (including how `a` and `b`
+/// were computed) may still leak. It is not a substitute for a vetted
+/// cryptographic library.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{ct_eq, smchash};
+///
+/// assert!(ct_eq(smchash(b"token"), smchash(b"token")));
+/// assert!(!ct_eq(smchash(b"token"), smchash(b"tokem")));
+/// ```
+#[must_use]
+pub fn ct_eq(a: u64, b: u64) -> bool {
+    ct_is_zero(a ^ b)
+}
+
+/// Compare two 128-bit values without branching on their contents.
+///
+/// Same guarantees and limitations as [`ct_eq`].
+#[must_use]
+pub fn ct_eq128(a: u128, b: u128) -> bool {
+    let diff = a ^ b;
+    ct_is_zero(diff as u64 | (diff >> 64) as u64)
+}
+
+/// Branch-free `diff == 0`
+#[inline]
+fn ct_is_zero(diff: u64) -> bool {
+    // Top bit of `diff | -diff` is set iff diff != 0
+    let ne = (diff | diff.wrapping_neg()) >> 63;
+    // SAFETY: reading a local through a valid reference. The volatile read
+    // stops the optimizer from turning the bit trick back into a branch.
+    let ne = unsafe { core::ptr::read_volatile(&ne) };
+    ne == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u64; 9] = [
+        0xf03aa5f0392b74c3,
+        0xa6ca3378b4cc6c35,
+        0xe1274b4b960f1ec5,
+        0xe46cac0f4d8d5933,
+        0xd4b8478d3cd42bc9,
+        0xa3d24bc96335711b,
+        0xb85a9a1e5578178d,
+        0x636596992ee8b499,
+        0x635cd16a99d18d4b,
+    ];
+
+    #[test]
+    fn test_mac_bit_flip() {
+        let mut data = [0u8; 200];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let tag = smc_mac(&data, &KEY);
+        assert!(smc_mac_verify(&data, &KEY, tag));
+        assert!(!smc_mac_verify(&data, &KEY, tag ^ 1));
+        assert!(!smc_mac_verify(&data, &KEY, tag ^ (1 << 63)));
+
+        for bit in 0..data.len() * 8 {
+            data[bit / 8] ^= 1 << (bit % 8);
+            assert!(!smc_mac_verify(&data, &KEY, tag), "bit {}", bit);
+            data[bit / 8] ^= 1 << (bit % 8);
+        }
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(ct_eq(0, 0));
+        assert!(ct_eq(u64::MAX, u64::MAX));
+        assert!(!ct_eq(0, 1));
+        assert!(!ct_eq(1 << 63, 0));
+
+        assert!(ct_eq128(0, 0));
+        assert!(ct_eq128(u128::MAX, u128::MAX));
+        assert!(!ct_eq128(0, 1));
+        assert!(!ct_eq128(1 << 64, 0));
+        assert!(!ct_eq128(1 << 127, 0));
+    }
+}
@@ -0,0 +1,74 @@
+//! Hashing plain-old-data values (requires the `bytemuck` feature).
+
+use crate::smchash_seeded;
+
+/// Hash the bytes of a [`Pod`](bytemuck::Pod) value.
+///
+/// Equal to `smchash_seeded(bytemuck::bytes_of(value), seed)`, for hashing
+/// small `#[repr(C)]` keys without serializing them by hand.
+///
+/// `Pod` rules out uninitialized padding, but not padding in the sense of
+/// bytes that do not affect equality: the hash covers every byte, so two
+/// values that compare equal must also be equal byte for byte. Lay `T` out
+/// without implicit padding (order fields by size, or add explicit
+/// zeroed `_pad` fields) and avoid floats, whose `0.0` and `-0.0`
+/// compare equal but differ in bits. The bytes are in native endianness,
+/// so multi-byte fields hash differently on big- and little-endian targets.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_pod, smchash_seeded};
+///
+/// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+/// #[repr(C)]
+/// struct Cell {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let cell = Cell { x: 3, y: -4 };
+/// assert_eq!(smchash_pod(&cell, 0), smchash_seeded(bytemuck::bytes_of(&cell), 0));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash_pod<T: bytemuck::Pod>(value: &T, seed: u64) -> u64 {
+    smchash_seeded(bytemuck::bytes_of(value), seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    #[repr(C)]
+    struct Key {
+        id: u64,
+        shard: u32,
+        kind: u16,
+        flags: u16,
+    }
+
+    #[test]
+    fn test_pod_matches_bytes() {
+        let key = Key {
+            id: 0x0123456789abcdef,
+            shard: 7,
+            kind: 2,
+            flags: 0x8001,
+        };
+        assert_eq!(core::mem::size_of::<Key>(), 16);
+        assert_eq!(
+            smchash_pod(&key, 5),
+            smchash_seeded(bytemuck::bytes_of(&key), 5)
+        );
+        assert_ne!(
+            smchash_pod(&key, 5),
+            smchash_pod(&Key { shard: 8, ..key }, 5)
+        );
+        assert_eq!(
+            smchash_pod(&42u64, 0),
+            smchash_seeded(&42u64.to_ne_bytes(), 0)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! smcHash's mixing primitives.
+//!
+//! These are the exact building blocks used by the hash and the PRNG,
+//! exposed for custom hashing schemes that want the same diffusion.
+
+use crate::SMC_SECRET;
+
+/// 128-bit multiply, XOR high and low halves.
+///
+/// Computes the full 128-bit product of `a` and `b` and folds it to 64 bits
+/// by XORing the high half into the low half.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::primitives::mix;
+///
+/// // 2^63 * 4 = 2^65: low half 0, high half 2
+/// assert_eq!(mix(1 << 63, 4), 2);
+/// ```
+#[cfg_attr(feature = "small", inline)]
+#[cfg_attr(not(feature = "small"), inline(always))]
+#[must_use]
+pub const fn mix(a: u64, b: u64) -> u64 {
+    let r = (a as u128) * (b as u128);
+    (r as u64) ^ ((r >> 64) as u64)
+}
+
+/// Multiply-update-mix, returning both outputs.
+///
+/// Computes the full 128-bit product of `a` and `b` and returns
+/// `(low ^ high, high)`: the first value is [`mix(a, b)`](mix), the second the
+/// high half of the product. This is the MUM step applied to the final state
+/// of every hash.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::primitives::{mix, mum_pair};
+///
+/// let (a, b) = mum_pair(0x9ad1e8e2aa5a5c4b, 12345);
+/// assert_eq!(a, mix(0x9ad1e8e2aa5a5c4b, 12345));
+/// assert_eq!(b, ((0x9ad1e8e2aa5a5c4b_u128 * 12345) >> 64) as u64);
+/// ```
+#[cfg_attr(feature = "small", inline)]
+#[cfg_attr(not(feature = "small"), inline(always))]
+#[must_use]
+pub const fn mum_pair(a: u64, b: u64) -> (u64, u64) {
+    let r = (a as u128) * (b as u128);
+    ((r as u64) ^ ((r >> 64) as u64), (r >> 64) as u64)
+}
+
+/// Combine a hash into an accumulator, order-sensitively.
+///
+/// Like `boost::hash_combine`: fold the hashes of a value's parts into one
+/// hash without rehashing their bytes. The result is order-sensitive, so
+/// `combine(combine(s, a), b)` differs from `combine(combine(s, b), a)`, and a
+/// single flipped bit in either argument changes about half the output bits.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{combine, smchash};
+///
+/// let fields = [smchash(b"alice"), smchash(b"42"), smchash(b"admin")];
+/// let h = fields.iter().fold(0, |acc, &f| combine(acc, f));
+///
+/// let swapped = [fields[1], fields[0], fields[2]];
+/// assert_ne!(h, swapped.iter().fold(0, |acc, &f| combine(acc, f)));
+/// ```
+#[cfg_attr(feature = "small", inline)]
+#[cfg_attr(not(feature = "small"), inline(always))]
+#[must_use]
+pub const fn combine(acc: u64, value: u64) -> u64 {
+    mix(acc ^ SMC_SECRET[3], value ^ SMC_SECRET[4])
+}
+
+/// Narrow a 128-bit hash to 64 bits, using both halves.
+///
+/// This is the recommended way to derive a 64-bit value, such as a bucket
+/// index, from a stored 128-bit hash: every input bit affects about half of
+/// the output bits. Plain truncation keeps only the low half; for
+/// [`smchash128`](crate::smchash128) that half equals [`smchash`](fn@crate::smchash)
+/// of the same data and is a good hash on its own, but it ignores the high
+/// half, and does nothing for 128-bit values whose halves are not each
+/// well mixed.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash128, fold128_to_64};
+///
+/// let h = smchash128(b"content");
+/// let bucket = fold128_to_64(h) % 1024;
+/// assert!(bucket < 1024);
+/// ```
+#[inline]
+#[must_use]
+pub const fn fold128_to_64(h: u128) -> u64 {
+    mix(h as u64 ^ SMC_SECRET[5], (h >> 64) as u64 ^ SMC_SECRET[6])
+}
+
+/// Fold a slice of hashes into one, order-sensitively.
+///
+/// Applies [`combine`] to each element in turn starting from `seed`, then
+/// combines the element count, so empty input gives a seed-dependent
+/// constant rather than `seed` itself and trailing elements are never
+/// ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{combine_slice, smchash};
+///
+/// let fields = [smchash(b"alice"), smchash(b"42"), smchash(b"admin")];
+/// let h = combine_slice(0, &fields);
+/// assert_ne!(h, combine_slice(0, &[fields[1], fields[0], fields[2]]));
+/// assert_ne!(combine_slice(0, &[]), combine_slice(1, &[]));
+/// ```
+#[inline]
+#[must_use]
+pub fn combine_slice(seed: u64, hashes: &[u64]) -> u64 {
+    let acc = hashes.iter().fold(seed, |acc, &h| combine(acc, h));
+    combine(acc, hashes.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::smc_rand;
+
+    #[test]
+    fn test_combine_order() {
+        let (a, b, c) = (1u64, 2u64, 3u64);
+        assert_ne!(combine(combine(0, a), b), combine(combine(0, b), a));
+        assert_ne!(
+            combine(combine(combine(0, a), b), c),
+            combine(combine(combine(0, a), c), b)
+        );
+        assert_ne!(combine(a, b), combine(b, a));
+        assert_ne!(combine(0, 0), 0);
+    }
+
+    #[test]
+    fn test_combine_slice() {
+        let hashes = [10u64, 20, 30, 40];
+        let h = combine_slice(7, &hashes);
+        assert_eq!(h, combine(hashes.iter().fold(7, |a, &x| combine(a, x)), 4));
+        assert_ne!(h, combine_slice(7, &[20, 10, 30, 40]));
+        assert_ne!(h, combine_slice(7, &[10, 20, 30, 40, 50]));
+        assert_ne!(h, combine_slice(7, &[10, 20, 30, 40, 0]));
+        assert_ne!(combine_slice(7, &[]), 7);
+        assert_ne!(combine_slice(7, &[]), combine_slice(8, &[]));
+    }
+
+    #[test]
+    fn test_combine_avalanche() {
+        // Every input bit should flip close to half of the 64 output bits
+        let mut seed = 11u64;
+        let trials = 2000;
+        for bit in 0..128 {
+            let mut flipped = 0u32;
+            for _ in 0..trials {
+                let (acc, value) = (smc_rand(&mut seed), smc_rand(&mut seed));
+                let h = combine(acc, value);
+                let h2 = if bit < 64 {
+                    combine(acc ^ (1 << bit), value)
+                } else {
+                    combine(acc, value ^ (1 << (bit - 64)))
+                };
+                flipped += (h ^ h2).count_ones();
+            }
+            let mean = flipped as f64 / trials as f64;
+            assert!((30.0..34.0).contains(&mean), "bit {} mean {}", bit, mean);
+        }
+    }
+
+    #[test]
+    fn test_fold128_avalanche() {
+        let mut seed = 90u64;
+        let trials = 2000;
+        for bit in 0..128 {
+            let mut flipped = 0u32;
+            for _ in 0..trials {
+                let h = ((smc_rand(&mut seed) as u128) << 64) | smc_rand(&mut seed) as u128;
+                flipped += (fold128_to_64(h) ^ fold128_to_64(h ^ (1 << bit))).count_ones();
+            }
+            let mean = flipped as f64 / trials as f64;
+            assert!((30.0..34.0).contains(&mean), "bit {} mean {}", bit, mean);
+        }
+        assert_ne!(fold128_to_64(0), 0);
+    }
+}
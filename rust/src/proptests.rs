@@ -0,0 +1,50 @@
+//! Randomized invariant tests over inputs of length 0..1024.
+
+use crate::*;
+use alloc::vec::Vec;
+use proptest::prelude::*;
+
+fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    proptest::collection::vec(any::<u8>(), 0..1024)
+}
+
+proptest! {
+    #[test]
+    fn deterministic(data in bytes(), seed in any::<u64>()) {
+        prop_assert_eq!(smchash_seeded(&data, seed), smchash_seeded(&data, seed));
+        prop_assert_eq!(smchash(&data), smchash(&data));
+        prop_assert_eq!(smchash_const(&data), smchash(&data));
+    }
+
+    #[test]
+    fn empty_input_any_seed(seed in any::<u64>()) {
+        let _ = smchash_seeded(b"", seed);
+        let _ = smchash128_seeded(b"", seed);
+        let _ = SmcHasher::with_seed(seed).finish();
+    }
+
+    #[test]
+    fn seeds_differ(
+        data in proptest::collection::vec(any::<u8>(), 1..1024),
+        s1 in any::<u64>(),
+        s2 in any::<u64>(),
+    ) {
+        prop_assume!(s1 != s2);
+        prop_assert_ne!(smchash_seeded(&data, s1), smchash_seeded(&data, s2));
+    }
+
+    #[test]
+    fn multi_matches_concat(
+        data in bytes(),
+        a in any::<prop::sample::Index>(),
+        b in any::<prop::sample::Index>(),
+        seed in any::<u64>(),
+    ) {
+        let (mut i, mut j) = (a.index(data.len() + 1), b.index(data.len() + 1));
+        if i > j {
+            core::mem::swap(&mut i, &mut j);
+        }
+        let parts: [&[u8]; 3] = [&data[..i], &data[i..j], &data[j..]];
+        prop_assert_eq!(smchash_multi(&parts, seed), smchash_seeded(&data, seed));
+    }
+}
@@ -0,0 +1,87 @@
+//! [`rand_core`] integration for [`SmcRng`] (requires the `rand-core` feature).
+
+use rand_core::{Error, RngCore, SeedableRng};
+
+use crate::{fill_bytes, smc_rand, SmcRng};
+
+impl RngCore for SmcRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        smc_rand(&mut self.seed)
+    }
+
+    /// Same output as the free [`fill_bytes`](crate::fill_bytes).
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes(&mut self.seed, dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for SmcRng {
+    /// The initial state as little-endian bytes
+    type Seed = [u8; 8];
+
+    #[inline]
+    fn from_seed(seed: [u8; 8]) -> Self {
+        SmcRng::new(u64::from_le_bytes(seed))
+    }
+
+    /// Use `state` directly as the initial state, matching [`SmcRng::new`].
+    #[inline]
+    fn seed_from_u64(state: u64) -> Self {
+        SmcRng::new(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_rng_core() {
+        let mut seed = 7u64;
+        let mut rng = SmcRng::seed_from_u64(7);
+        assert_eq!(rng.next_u64(), smc_rand(&mut seed));
+        assert_eq!(rng.next_u32(), (smc_rand(&mut seed) >> 32) as u32);
+
+        let rng = SmcRng::from_seed(7u64.to_le_bytes());
+        assert_eq!(
+            rng.take(3).collect::<alloc::vec::Vec<_>>(),
+            SmcRng::new(7).take(3).collect::<alloc::vec::Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        let mut seed = 3u64;
+        let words = [smc_rand(&mut seed), smc_rand(&mut seed)];
+
+        let mut buf = [0u8; 13];
+        SmcRng::new(3).fill_bytes(&mut buf);
+        assert_eq!(buf[..8], words[0].to_le_bytes());
+        assert_eq!(buf[8..], words[1].to_le_bytes()[..5]);
+    }
+
+    #[test]
+    fn test_gen_range() {
+        let mut rng = SmcRng::new(42);
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            let x: usize = rng.gen_range(0..10);
+            seen[x] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+}
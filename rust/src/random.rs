@@ -0,0 +1,129 @@
+//! Thread-local convenience RNG (requires the `std` feature).
+
+use core::cell::Cell;
+
+use crate::{smc_rand, smc_rand_f64, smc_rand_range};
+
+/// A fresh seed from process entropy.
+///
+/// With the `getrandom` feature this is [`random_seed`](crate::random_seed),
+/// read from the operating system's secure RNG.
+#[cfg(feature = "getrandom")]
+pub(crate) fn entropy_seed() -> u64 {
+    crate::random_seed()
+}
+
+/// A fresh seed from process entropy.
+///
+/// Without the `getrandom` feature: std's `RandomState` is keyed from OS
+/// entropy once per thread and advanced for every instance, so each call
+/// yields a new value. The address of a stack local is mixed in as well,
+/// which differs between threads even if the platform's entropy source is
+/// weak.
+#[cfg(not(feature = "getrandom"))]
+pub(crate) fn entropy_seed() -> u64 {
+    use core::hash::{BuildHasher, Hasher};
+
+    use crate::{mix, SMC_SECRET};
+
+    let key = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    let local = 0u8;
+    let addr = &local as *const u8 as usize as u64;
+    key ^ mix(addr ^ SMC_SECRET[5], SMC_SECRET[6])
+}
+
+std::thread_local! {
+    /// Per-thread generator state, seeded on first use in each thread
+    static STATE: Cell<u64> = Cell::new(entropy_seed());
+}
+
+/// Run `f` on this thread's generator state
+#[inline]
+fn with_state<T>(f: impl FnOnce(&mut u64) -> T) -> T {
+    STATE.with(|state| {
+        let mut seed = state.get();
+        let out = f(&mut seed);
+        state.set(seed);
+        out
+    })
+}
+
+/// Random `u64` from a thread-local generator.
+///
+/// Each thread's generator is seeded once from entropy on first use, so
+/// output differs between runs and between threads. For reproducible
+/// sequences use [`smc_rand`] or [`SmcRng`](crate::SmcRng) with a fixed seed.
+///
+/// # Example
+///
+/// ```rust
+/// let a = smchash::random();
+/// let b = smchash::random();
+/// assert_ne!(a, b);
+/// ```
+#[inline]
+#[must_use]
+pub fn random() -> u64 {
+    with_state(smc_rand)
+}
+
+/// Random integer in `[0, bound)` from the thread-local generator.
+///
+/// See [`smc_rand_range`].
+///
+/// # Panics
+///
+/// Panics if `bound` is zero.
+#[inline]
+#[must_use]
+pub fn random_range(bound: u64) -> u64 {
+    with_state(|seed| smc_rand_range(seed, bound))
+}
+
+/// Random `f64` in `[0.0, 1.0)` from the thread-local generator.
+///
+/// See [`smc_rand_f64`].
+#[inline]
+#[must_use]
+pub fn random_f64() -> f64 {
+    with_state(smc_rand_f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_entropy_seed() {
+        assert_ne!(entropy_seed(), entropy_seed());
+    }
+
+    #[test]
+    fn test_random() {
+        let a = random();
+        let b = random();
+        assert_ne!(a, b);
+
+        for _ in 0..1000 {
+            assert!(random_range(10) < 10);
+            assert!((0.0..1.0).contains(&random_f64()));
+        }
+    }
+
+    #[test]
+    fn test_random_threads_independent() {
+        let handles: Vec<_> = (0..4)
+            .map(|_| std::thread::spawn(|| (0..100).map(|_| random()).collect::<Vec<u64>>()))
+            .collect();
+        let mut all: Vec<u64> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        all.sort_unstable();
+        all.dedup();
+        assert_eq!(all.len(), 400);
+    }
+}
@@ -0,0 +1,994 @@
+//! Helpers built on top of [`smc_rand`].
+
+use crate::primitives::{mix, mum_pair};
+use crate::{smc_rand, SMC_SECRET};
+
+/// Generate a uniformly distributed integer in `[0, bound)`.
+///
+/// Uses Lemire's multiply-shift method with rejection, so there is no modulo
+/// bias for any `bound`, power of two or not. The seed advances at least once
+/// and, rarely, a few more times when a sample is rejected.
+///
+/// # Panics
+///
+/// Panics if `bound` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smc_rand_range;
+///
+/// let mut seed = 42u64;
+/// let die = smc_rand_range(&mut seed, 6) + 1;
+/// assert!((1..=6).contains(&die));
+/// ```
+#[must_use]
+pub fn smc_rand_range(seed: &mut u64, bound: u64) -> u64 {
+    assert!(bound != 0, "smc_rand_range: bound must be non-zero");
+    range_with(&mut || smc_rand(seed), bound)
+}
+
+/// Lemire's method over an arbitrary `u64` source. `bound` must be non-zero.
+#[inline]
+fn range_with(next: &mut impl FnMut() -> u64, bound: u64) -> u64 {
+    let mut m = (next() as u128) * (bound as u128);
+    if (m as u64) < bound {
+        // 2^64 mod bound: low products below this would over-represent some outputs
+        let threshold = bound.wrapping_neg() % bound;
+        while (m as u64) < threshold {
+            m = (next() as u128) * (bound as u128);
+        }
+    }
+    (m >> 64) as u64
+}
+
+/// Generate a uniformly distributed integer in `[lo, hi]`.
+///
+/// Built on [`smc_rand_range`], so it is unbiased for every span. The full
+/// range `[0, u64::MAX]` is a single [`smc_rand`] output with no rejection,
+/// and `lo == hi` returns `lo`. The seed always advances at least once.
+///
+/// # Panics
+///
+/// Panics if `lo > hi`.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smc_rand_range_inclusive;
+///
+/// let mut seed = 42u64;
+/// let die = smc_rand_range_inclusive(&mut seed, 1, 6);
+/// assert!((1..=6).contains(&die));
+/// ```
+#[must_use]
+pub fn smc_rand_range_inclusive(seed: &mut u64, lo: u64, hi: u64) -> u64 {
+    assert!(lo <= hi, "smc_rand_range_inclusive: lo must not exceed hi");
+
+    let span = hi - lo;
+    if span == u64::MAX {
+        return smc_rand(seed);
+    }
+    lo + smc_rand_range(seed, span + 1)
+}
+
+/// Generate a uniformly distributed `f64` in `[0.0, 1.0)`.
+///
+/// Takes the top 53 bits of one [`smc_rand`] output and scales by `2^-53`, so
+/// every representable multiple of `2^-53` in the range is equally likely and
+/// the result is never exactly `1.0`.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smc_rand_f64;
+///
+/// let mut seed = 42u64;
+/// let x = smc_rand_f64(&mut seed);
+/// assert!((0.0..1.0).contains(&x));
+/// ```
+#[inline]
+#[must_use]
+pub fn smc_rand_f64(seed: &mut u64) -> f64 {
+    (smc_rand(seed) >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+/// Generate a uniformly distributed `f32` in `[0.0, 1.0)`.
+///
+/// Like [`smc_rand_f64`], using the top 24 bits scaled by `2^-24`.
+#[inline]
+#[must_use]
+pub fn smc_rand_f32(seed: &mut u64) -> f32 {
+    (smc_rand(seed) >> 40) as f32 * (1.0 / (1u32 << 24) as f32)
+}
+
+/// Fill `out` with uniformly distributed `f64`s in `[0.0, 1.0)`.
+///
+/// Produces exactly the values of calling [`smc_rand_f64`] once per element
+/// from the same seed, and leaves `seed` in the same state. The state is
+/// kept in a local for the whole loop, so the generator and the float
+/// conversion compile to a tight loop the optimizer can unroll and
+/// vectorize.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smc_rand_fill_f64;
+///
+/// let mut seed = 42u64;
+/// let mut samples = [0.0f64; 1024];
+/// smc_rand_fill_f64(&mut seed, &mut samples);
+/// assert!(samples.iter().all(|&x| (0.0..1.0).contains(&x)));
+/// ```
+pub fn smc_rand_fill_f64(seed: &mut u64, out: &mut [f64]) {
+    let mut state = *seed;
+    for x in out.iter_mut() {
+        *x = smc_rand_f64(&mut state);
+    }
+    *seed = state;
+}
+
+/// Generate a 128-bit pseudo-random value, e.g. for UUID-like identifiers.
+///
+/// Advances `seed` exactly like [`smc_rand`], so the two can be mixed on one
+/// seed. The low 64 bits are the value [`smc_rand`] would have returned from
+/// the same state. The high 64 bits are the high half of the same 128-bit
+/// product, whitened by one more multiply-mix: the raw halves are not
+/// independent (bit 0 of the product is always clear, so both halves would
+/// share their lowest bit).
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smc_rand, smc_rand128};
+///
+/// let (mut a, mut b) = (42u64, 42u64);
+/// let wide = smc_rand128(&mut a);
+/// assert_eq!(wide as u64, smc_rand(&mut b));
+/// assert_eq!(a, b);
+/// ```
+#[inline]
+#[must_use]
+pub fn smc_rand128(seed: &mut u64) -> u128 {
+    *seed = seed.wrapping_add(SMC_SECRET[0]);
+    let (lo, hi) = mum_pair(*seed, *seed ^ SMC_SECRET[1]);
+    let hi = mix(hi ^ SMC_SECRET[2], lo ^ SMC_SECRET[3]);
+    ((hi as u128) << 64) | lo as u128
+}
+
+/// Generate a uniformly random `bool`.
+///
+/// Uses the top bit of [`smc_rand`] rather than its parity, since the high
+/// bits of a multiply-mix output are the best mixed.
+#[inline]
+#[must_use]
+pub fn smc_rand_bool(seed: &mut u64) -> bool {
+    smc_rand(seed) >> 63 != 0
+}
+
+/// Generate a `bool` that is `true` with probability `p`.
+///
+/// `p` is clamped to `[0.0, 1.0]`, so `0.0` (or less) is always `false` and
+/// `1.0` (or more) is always `true`. A NaN `p` is always `false`.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smc_rand_bool_p;
+///
+/// let mut seed = 42u64;
+/// let _crit = smc_rand_bool_p(&mut seed, 0.15);
+/// assert!(!smc_rand_bool_p(&mut seed, 0.0));
+/// ```
+#[inline]
+#[must_use]
+pub fn smc_rand_bool_p(seed: &mut u64, p: f64) -> bool {
+    smc_rand_f64(seed) < p.clamp(0.0, 1.0)
+}
+
+/// Generate a uniformly random `i64` over the full signed range.
+#[inline]
+#[must_use]
+pub fn smc_rand_i64(seed: &mut u64) -> i64 {
+    smc_rand(seed) as i64
+}
+
+/// Generate a normally distributed `f64` (requires `std`).
+///
+/// Uses Marsaglia's polar method on pairs of [`smc_rand_f64`] draws, keeping
+/// one of the two values it produces so no state is carried between calls.
+/// Each call advances the seed by an even number of steps (about 2.5 pairs on
+/// average). If `std_dev` is zero, returns `mean` without advancing the seed.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smc_rand_normal;
+///
+/// let mut seed = 42u64;
+/// let height = smc_rand_normal(&mut seed, 170.0, 8.0);
+/// ```
+#[cfg(feature = "std")]
+#[must_use]
+pub fn smc_rand_normal(seed: &mut u64, mean: f64, std_dev: f64) -> f64 {
+    if std_dev == 0.0 {
+        return mean;
+    }
+    loop {
+        let u = 2.0 * smc_rand_f64(seed) - 1.0;
+        let v = 2.0 * smc_rand_f64(seed) - 1.0;
+        let s = u * u + v * v;
+        if s > 0.0 && s < 1.0 {
+            return mean + std_dev * u * (-2.0 * s.ln() / s).sqrt();
+        }
+    }
+}
+
+/// Fill `dst` with pseudo-random bytes.
+///
+/// Each [`smc_rand`] output is written as 8 little-endian bytes. A trailing
+/// partial chunk of 1-7 bytes consumes one more output and takes its low
+/// bytes, so the seed always advances by `dst.len().div_ceil(8)` steps.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::fill_bytes;
+///
+/// let mut seed = 42u64;
+/// let mut nonce = [0u8; 12];
+/// fill_bytes(&mut seed, &mut nonce);
+/// ```
+pub fn fill_bytes(seed: &mut u64, dst: &mut [u8]) {
+    let mut chunks = dst.chunks_exact_mut(8);
+    for chunk in &mut chunks {
+        chunk.copy_from_slice(&smc_rand(seed).to_le_bytes());
+    }
+    let rest = chunks.into_remainder();
+    if !rest.is_empty() {
+        let n = rest.len();
+        rest.copy_from_slice(&smc_rand(seed).to_le_bytes()[..n]);
+    }
+}
+
+const ALPHANUMERIC: &[u8; 62] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Fill `dst` with random ASCII letters and digits (`[A-Za-z0-9]`).
+///
+/// Each byte is chosen with [`smc_rand_range`], so all 62 characters are
+/// equally likely. The output is reproducible for a given starting seed.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::fill_alphanumeric;
+///
+/// let mut seed = 42u64;
+/// let mut id = [0u8; 12];
+/// fill_alphanumeric(&mut seed, &mut id);
+/// assert!(id.iter().all(u8::is_ascii_alphanumeric));
+/// ```
+pub fn fill_alphanumeric(seed: &mut u64, dst: &mut [u8]) {
+    for b in dst.iter_mut() {
+        *b = ALPHANUMERIC[smc_rand_range(seed, 62) as usize];
+    }
+}
+
+/// Generate a random `[A-Za-z0-9]` string of `len` characters (requires the
+/// `alloc` feature).
+///
+/// Same characters as [`fill_alphanumeric`] on a `len`-byte buffer.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::random_alphanumeric;
+///
+/// let mut seed = 42u64;
+/// let token = random_alphanumeric(&mut seed, 16);
+/// assert_eq!(token.len(), 16);
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn random_alphanumeric(seed: &mut u64, len: usize) -> alloc::string::String {
+    let mut bytes = alloc::vec![0u8; len];
+    fill_alphanumeric(seed, &mut bytes);
+    // Every byte is ASCII
+    bytes.into_iter().map(char::from).collect()
+}
+
+/// Generate 32 pseudo-random bytes on the stack.
+///
+/// Advances the seed exactly four steps and lays out the outputs as
+/// little-endian bytes, so the result equals [`fill_bytes`] into a 32-byte
+/// buffer.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::random_block;
+///
+/// let mut seed = 42u64;
+/// let nonce = random_block(&mut seed);
+/// assert_ne!(nonce, random_block(&mut seed));
+/// ```
+#[must_use]
+pub fn random_block(seed: &mut u64) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for chunk in out.chunks_exact_mut(8) {
+        chunk.copy_from_slice(&smc_rand(seed).to_le_bytes());
+    }
+    out
+}
+
+/// Shuffle `slice` in place with an unbiased Fisher-Yates shuffle.
+///
+/// Each swap index comes from [`smc_rand_range`], so every permutation is
+/// equally likely. The result is reproducible for a given starting seed.
+/// Empty and single-element slices are left untouched and do not advance the
+/// seed.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::shuffle;
+///
+/// let mut seed = 42u64;
+/// let mut deck: Vec<u32> = (0..52).collect();
+/// shuffle(&mut seed, &mut deck);
+/// ```
+pub fn shuffle<T>(seed: &mut u64, slice: &mut [T]) {
+    shuffle_with(slice, &mut || smc_rand(seed));
+}
+
+/// Shuffle `slice` in place, drawing randomness from any `u64` source.
+///
+/// Same unbiased Fisher-Yates as [`shuffle`], with swap indices taken from
+/// `rng` by the rejection method of [`smc_rand_range`]. `rng` should return
+/// uniformly distributed `u64`s; it is called at least once per element after
+/// the first, and empty or single-element slices never call it.
+/// `shuffle(&mut seed, slice)` is `shuffle_with(slice, &mut || smc_rand(&mut seed))`.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{shuffle, shuffle_with, smc_rand};
+///
+/// let mut a: Vec<u32> = (0..52).collect();
+/// let mut b = a.clone();
+///
+/// let mut seed = 42u64;
+/// shuffle_with(&mut a, &mut || smc_rand(&mut seed));
+/// shuffle(&mut 42, &mut b);
+/// assert_eq!(a, b);
+/// ```
+pub fn shuffle_with<T>(slice: &mut [T], rng: &mut impl FnMut() -> u64) {
+    for i in (1..slice.len()).rev() {
+        let j = range_with(rng, i as u64 + 1) as usize;
+        slice.swap(i, j);
+    }
+}
+
+/// Pick a uniformly random element of `slice`.
+///
+/// Returns `None` for an empty slice, without advancing the seed.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::choose;
+///
+/// let mut seed = 42u64;
+/// let color = choose(&mut seed, &["red", "green", "blue"]);
+/// assert!(color.is_some());
+/// ```
+#[must_use]
+pub fn choose<'a, T>(seed: &mut u64, slice: &'a [T]) -> Option<&'a T> {
+    if slice.is_empty() {
+        return None;
+    }
+    Some(&slice[smc_rand_range(seed, slice.len() as u64) as usize])
+}
+
+/// Pick an index with probability proportional to its weight.
+///
+/// Draws one [`smc_rand_range`] over the total weight and scans for the
+/// matching index, so zero-weight entries are never chosen. Returns `None`,
+/// without advancing the seed, if `weights` is empty or all zero.
+///
+/// # Panics
+///
+/// Panics if the weights sum to more than `u64::MAX`.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::weighted_choice;
+///
+/// let mut seed = 42u64;
+/// // common, rare, legendary
+/// let loot = weighted_choice(&mut seed, &[90, 9, 1]).unwrap();
+/// assert!(loot < 3);
+/// ```
+#[must_use]
+pub fn weighted_choice(seed: &mut u64, weights: &[u64]) -> Option<usize> {
+    let total = weights.iter().try_fold(0u64, |acc, &w| acc.checked_add(w));
+    let total = total.expect("weighted_choice: total weight overflows u64");
+    if total == 0 {
+        return None;
+    }
+    let mut r = smc_rand_range(seed, total);
+    for (i, &w) in weights.iter().enumerate() {
+        if r < w {
+            return Some(i);
+        }
+        r -= w;
+    }
+    unreachable!("r < total")
+}
+
+/// Pick `k` distinct indices from `0..n` uniformly at random (requires `alloc`).
+///
+/// Uses a partial Fisher-Yates shuffle of `0..n`, so every `k`-subset is
+/// equally likely and the indices come out in random order. If `k > n`, all
+/// `n` indices are returned. Allocates `n` indices, so keep `n` modest.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::sample_indices;
+///
+/// let mut seed = 42u64;
+/// let picks = sample_indices(&mut seed, 100, 5);
+/// assert_eq!(picks.len(), 5);
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn sample_indices(seed: &mut u64, n: usize, k: usize) -> alloc::vec::Vec<usize> {
+    let k = k.min(n);
+    let mut indices: alloc::vec::Vec<usize> = (0..n).collect();
+    for i in 0..k {
+        let j = i + smc_rand_range(seed, (n - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+    indices.truncate(k);
+    indices
+}
+
+/// Return a reproducible permutation of `0..n` (requires the `alloc` feature).
+///
+/// Equal to running [`shuffle`] on `(0..n).collect()` starting from `seed`.
+/// The seed is taken by value, so the same `(seed, n)` always gives the same
+/// order without threading generator state through the caller.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::permutation;
+///
+/// let order = permutation(7, 5);
+/// assert_eq!(order, permutation(7, 5));
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn permutation(mut seed: u64, n: usize) -> alloc::vec::Vec<usize> {
+    let mut order: alloc::vec::Vec<usize> = (0..n).collect();
+    shuffle(&mut seed, &mut order);
+    order
+}
+
+/// Owned [`smc_rand`] generator.
+///
+/// Yields exactly the same sequence as calling [`smc_rand`] repeatedly on the
+/// same starting seed. The iterator never ends.
+///
+/// With the `rand-core` feature, `SmcRng` also implements `rand_core::RngCore`
+/// and `rand_core::SeedableRng`, so it works with the `rand` ecosystem.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::SmcRng;
+///
+/// let values: Vec<u64> = SmcRng::new(1).take(100).collect();
+/// assert_eq!(values.len(), 100);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SmcRng {
+    pub(crate) seed: u64,
+}
+
+impl SmcRng {
+    /// Create a generator starting from `seed`.
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        SmcRng { seed }
+    }
+
+    /// Advance the generator by 2^32 steps in O(1).
+    ///
+    /// [`smc_rand`] adds a fixed odd constant to the state on every step, so
+    /// skipping ahead is a single multiply-add. Cloning a generator and
+    /// jumping the clone `k` times gives a substream starting `k * 2^32`
+    /// outputs later, so streams handed to different threads this way do not
+    /// overlap as long as each draws fewer than 2^32 values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use smchash::SmcRng;
+    ///
+    /// let mut base = SmcRng::new(42);
+    /// let streams: Vec<SmcRng> = (0..4)
+    ///     .map(|_| {
+    ///         let stream = base.clone();
+    ///         base.jump();
+    ///         stream
+    ///     })
+    ///     .collect();
+    /// ```
+    #[inline]
+    pub fn jump(&mut self) {
+        self.seed = self.seed.wrapping_add(SMC_SECRET[0].wrapping_mul(1 << 32));
+    }
+}
+
+impl Default for SmcRng {
+    /// A generator with the fixed seed 0, same as `SmcRng::new(0)`.
+    ///
+    /// This is deterministic, not random: every default generator yields the
+    /// same sequence.
+    #[inline]
+    fn default() -> Self {
+        SmcRng::new(0)
+    }
+}
+
+impl Iterator for SmcRng {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        Some(smc_rand(&mut self.seed))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_with_fixed_sequence() {
+        // 0 is rejected for bound 5 (2^64 mod 5 = 1), then the swap indices
+        // are 2, 3, 0 and 1
+        let seq = [0, 1 << 63, u64::MAX, 1 << 62, 1 << 63];
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            seq[calls - 1]
+        };
+        let mut v = [0, 1, 2, 3, 4];
+        shuffle_with(&mut v, &mut next);
+        assert_eq!(v, [4, 1, 0, 3, 2]);
+        assert_eq!(calls, seq.len());
+
+        let mut one = [9];
+        shuffle_with(&mut one, &mut || unreachable!());
+        assert_eq!(one, [9]);
+    }
+
+    #[test]
+    fn test_shuffle_with_matches_shuffle() {
+        let mut a: [u32; 100] = core::array::from_fn(|i| i as u32);
+        let mut b = a;
+        let mut seed = 99u64;
+        shuffle_with(&mut a, &mut || smc_rand(&mut seed));
+        let mut other = 99u64;
+        shuffle(&mut other, &mut b);
+        assert_eq!(a, b);
+        assert_eq!(seed, other);
+    }
+
+    #[test]
+    fn test_fill_alphanumeric() {
+        let mut seed = 97u64;
+        let mut buf = [0u8; 62_000];
+        fill_alphanumeric(&mut seed, &mut buf);
+        let mut counts = [0u32; 128];
+        for &b in &buf {
+            assert!(b.is_ascii_alphanumeric());
+            counts[b as usize] += 1;
+        }
+        for &c in ALPHANUMERIC {
+            assert!((850..1150).contains(&counts[c as usize]), "{}", c as char);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_random_alphanumeric() {
+        let (mut a, mut b) = (5u64, 5u64);
+        let s = random_alphanumeric(&mut a, 40);
+        assert_eq!(s.len(), 40);
+        assert!(s.bytes().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(s, random_alphanumeric(&mut b, 40));
+        assert_ne!(random_alphanumeric(&mut a, 40), s);
+        assert!(random_alphanumeric(&mut a, 0).is_empty());
+
+        let mut c = 5u64;
+        let mut buf = [0u8; 40];
+        fill_alphanumeric(&mut c, &mut buf);
+        assert_eq!(s.as_bytes(), buf);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_permutation() {
+        let p = permutation(7, 5);
+        assert_eq!(p, permutation(7, 5));
+        let mut sorted = p.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, [0, 1, 2, 3, 4]);
+
+        let mut seed = 7u64;
+        let mut expected: alloc::vec::Vec<usize> = (0..1000).collect();
+        shuffle(&mut seed, &mut expected);
+        assert_eq!(permutation(7, 1000), expected);
+        assert!(permutation(7, 0).is_empty());
+    }
+
+    #[test]
+    fn test_rand128() {
+        let (mut a, mut b) = (123u64, 123u64);
+        let mut same_low_bit = 0;
+        for _ in 0..10_000 {
+            let wide = smc_rand128(&mut a);
+            assert_eq!(wide as u64, smc_rand(&mut b));
+            assert_eq!(a, b);
+            if (wide ^ (wide >> 64)) & 1 == 0 {
+                same_low_bit += 1;
+            }
+        }
+        assert!((4_700..5_300).contains(&same_low_bit), "{}", same_low_bit);
+    }
+
+    #[test]
+    fn test_rand_fill_f64() {
+        let (mut a, mut b) = (77u64, 77u64);
+        let mut filled = [0.0f64; 1000];
+        smc_rand_fill_f64(&mut a, &mut filled);
+        for &x in &filled {
+            assert_eq!(x, smc_rand_f64(&mut b));
+        }
+        assert_eq!(a, b);
+
+        smc_rand_fill_f64(&mut a, &mut []);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_rand_bool() {
+        let mut seed = 11u64;
+        let trues = (0..10_000).filter(|_| smc_rand_bool(&mut seed)).count();
+        assert!((4_700..5_300).contains(&trues), "{}", trues);
+
+        for _ in 0..1000 {
+            assert!(!smc_rand_bool_p(&mut seed, 0.0));
+            assert!(!smc_rand_bool_p(&mut seed, -1.0));
+            assert!(smc_rand_bool_p(&mut seed, 1.0));
+            assert!(smc_rand_bool_p(&mut seed, 2.0));
+        }
+        let hits = (0..10_000)
+            .filter(|_| smc_rand_bool_p(&mut seed, 0.25))
+            .count();
+        assert!((2_300..2_700).contains(&hits), "{}", hits);
+    }
+
+    #[test]
+    fn test_rand_i64() {
+        let mut seed = 5u64;
+        let negatives = (0..10_000).filter(|_| smc_rand_i64(&mut seed) < 0).count();
+        assert!((4_700..5_300).contains(&negatives), "{}", negatives);
+    }
+
+    #[test]
+    fn test_rand_range_inclusive() {
+        let mut seed = 3u64;
+        let mut counts = [0u32; 5];
+        for _ in 0..50_000 {
+            let x = smc_rand_range_inclusive(&mut seed, 10, 14);
+            counts[(x - 10) as usize] += 1;
+        }
+        for &c in &counts {
+            assert!((9_500..10_500).contains(&c), "{:?}", counts);
+        }
+
+        // Full range is exactly one raw output
+        let (mut a, mut b) = (9u64, 9u64);
+        assert_eq!(
+            smc_rand_range_inclusive(&mut a, 0, u64::MAX),
+            smc_rand(&mut b)
+        );
+        assert_eq!(a, b);
+
+        assert_eq!(smc_rand_range_inclusive(&mut seed, 7, 7), 7);
+        let top = smc_rand_range_inclusive(&mut seed, u64::MAX - 1, u64::MAX);
+        assert!(top >= u64::MAX - 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rand_range_inclusive_reversed() {
+        let _ = smc_rand_range_inclusive(&mut 0, 2, 1);
+    }
+
+    #[test]
+    fn test_rng_default() {
+        let mut seed = 0u64;
+        let mut rng = SmcRng::default();
+        for _ in 0..10 {
+            assert_eq!(rng.next(), Some(smc_rand(&mut seed)));
+        }
+    }
+
+    #[test]
+    fn test_range_bounds() {
+        let mut seed = 1u64;
+        for &bound in &[1u64, 2, 3, 7, 10, 1000, u64::MAX / 3 + 1, u64::MAX] {
+            for _ in 0..1000 {
+                assert!(smc_rand_range(&mut seed, bound) < bound);
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_uniform() {
+        let mut seed = 12345u64;
+        let mut counts = [0u32; 10];
+        let samples = 100_000;
+        for _ in 0..samples {
+            counts[smc_rand_range(&mut seed, 10) as usize] += 1;
+        }
+        let expected = samples / 10;
+        for &c in &counts {
+            assert!(c.abs_diff(expected) < expected / 20, "counts {:?}", counts);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_zero() {
+        let mut seed = 0u64;
+        let _ = smc_rand_range(&mut seed, 0);
+    }
+
+    #[test]
+    fn test_rand_float() {
+        let mut seed = 7u64;
+        let (mut low64, mut low32) = (0u64, 0u32);
+        let (mut min, mut max) = (1.0f64, 0.0f64);
+        for _ in 0..100_000 {
+            let x = smc_rand_f64(&mut seed);
+            assert!((0.0..1.0).contains(&x));
+            low64 |= (x * (1u64 << 53) as f64) as u64 & 1;
+            min = min.min(x);
+            max = max.max(x);
+
+            let y = smc_rand_f32(&mut seed);
+            assert!((0.0..1.0).contains(&y));
+            low32 |= (y * (1u32 << 24) as f32) as u32 & 1;
+        }
+        // Full precision: the lowest mantissa bit is used
+        assert_eq!((low64, low32), (1, 1));
+        assert!(min < 0.001 && max > 0.999);
+
+        // Largest possible output still rounds below 1.0
+        assert!(((u64::MAX >> 11) as f64 * (1.0 / (1u64 << 53) as f64)) < 1.0);
+        assert!(((u64::MAX >> 40) as f32 * (1.0 / (1u32 << 24) as f32)) < 1.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_rand_normal() {
+        let mut seed = 17u64;
+        let n = 200_000;
+        let (mut sum, mut sum_sq) = (0.0f64, 0.0f64);
+        for _ in 0..n {
+            let x = smc_rand_normal(&mut seed, 5.0, 2.0);
+            sum += x;
+            sum_sq += x * x;
+        }
+        let mean = sum / n as f64;
+        let variance = sum_sq / n as f64 - mean * mean;
+        assert!((mean - 5.0).abs() < 0.02, "mean {}", mean);
+        assert!((variance - 4.0).abs() < 0.05, "variance {}", variance);
+
+        let before = seed;
+        assert_eq!(smc_rand_normal(&mut seed, 3.5, 0.0), 3.5);
+        assert_eq!(seed, before);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut seed = 5u64;
+        let mut empty: [u8; 0] = [];
+        shuffle(&mut seed, &mut empty);
+        let mut one = [1u8];
+        shuffle(&mut seed, &mut one);
+        assert_eq!((one, seed), ([1], 5));
+
+        let original: alloc::vec::Vec<u32> = (0..100).collect();
+        let mut a = original.clone();
+        let mut b = original.clone();
+        let (mut seed_a, mut seed_b) = (9u64, 9u64);
+        shuffle(&mut seed_a, &mut a);
+        shuffle(&mut seed_b, &mut b);
+        assert_eq!(a, b);
+        assert_ne!(a, original);
+
+        a.sort_unstable();
+        assert_eq!(a, original);
+    }
+
+    #[test]
+    fn test_shuffle_uniform() {
+        // All 6 permutations of 3 elements should be about equally likely
+        let mut seed = 1u64;
+        let mut counts = [0u32; 9];
+        for _ in 0..60_000 {
+            let mut v = [0usize, 1, 2];
+            shuffle(&mut seed, &mut v);
+            counts[v[0] * 3 + v[1]] += 1;
+        }
+        for (i, &c) in counts.iter().enumerate() {
+            // Only codes with v[0] != v[1] are permutations
+            let expected = if i / 3 == i % 3 { 0 } else { 10_000 };
+            assert!(c.abs_diff(expected) < 500, "counts {:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_choose() {
+        let mut seed = 3u64;
+        assert_eq!(choose::<u8>(&mut seed, &[]), None);
+        assert_eq!(seed, 3);
+        assert_eq!(choose(&mut seed, &[7]), Some(&7));
+
+        let items = [10, 20, 30, 40];
+        let mut seen = [false; 4];
+        for _ in 0..1000 {
+            let &x = choose(&mut seed, &items).unwrap();
+            seen[x / 10 - 1] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+    }
+
+    #[test]
+    fn test_weighted_choice() {
+        let mut seed = 6u64;
+        assert_eq!(weighted_choice(&mut seed, &[]), None);
+        assert_eq!(weighted_choice(&mut seed, &[0, 0]), None);
+        assert_eq!(seed, 6);
+        assert_eq!(weighted_choice(&mut seed, &[0, 5, 0]), Some(1));
+
+        let mut counts = [0u32; 3];
+        for _ in 0..40_000 {
+            counts[weighted_choice(&mut seed, &[1, 0, 3]).unwrap()] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!(counts[0].abs_diff(10_000) < 500, "counts {:?}", counts);
+        assert!(counts[2].abs_diff(30_000) < 500, "counts {:?}", counts);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_weighted_choice_overflow() {
+        let mut seed = 0u64;
+        let _ = weighted_choice(&mut seed, &[u64::MAX, 1]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_sample_indices() {
+        let mut seed = 4u64;
+        for &(n, k) in &[
+            (0, 0),
+            (0, 3),
+            (10, 0),
+            (10, 3),
+            (10, 10),
+            (10, 25),
+            (1000, 50),
+        ] {
+            let mut picks = sample_indices(&mut seed, n, k);
+            assert_eq!(picks.len(), k.min(n));
+            assert!(picks.iter().all(|&i| i < n));
+            picks.sort_unstable();
+            picks.dedup();
+            assert_eq!(picks.len(), k.min(n), "indices not distinct");
+        }
+
+        let mut a = 8u64;
+        let mut b = 8u64;
+        assert_eq!(sample_indices(&mut a, 50, 7), sample_indices(&mut b, 50, 7));
+    }
+
+    #[test]
+    fn test_rng_matches_smc_rand() {
+        let mut seed = 42u64;
+        let mut rng = SmcRng::new(42);
+        for _ in 0..5 {
+            assert_eq!(rng.next(), Some(smc_rand(&mut seed)));
+        }
+    }
+
+    #[test]
+    fn test_jump() {
+        // The state advances by SMC_SECRET[0] per step
+        let mut seed = 42u64;
+        let _ = smc_rand(&mut seed);
+        assert_eq!(seed, 42u64.wrapping_add(SMC_SECRET[0]));
+
+        let mut a = SmcRng::new(42);
+        let mut b = a.clone();
+        b.jump();
+        assert_eq!(
+            b.seed,
+            42u64.wrapping_add(SMC_SECRET[0].wrapping_mul(1 << 32))
+        );
+
+        let mut outputs: alloc::vec::Vec<u64> = (&mut a).take(1000).collect();
+        outputs.extend((&mut b).take(1000));
+        outputs.sort_unstable();
+        outputs.dedup();
+        assert_eq!(outputs.len(), 2000);
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        for len in [13usize, 64] {
+            let mut buf1 = [0u8; 64];
+            let mut buf2 = [0u8; 64];
+            let mut seed1 = 99u64;
+            let mut seed2 = 99u64;
+            fill_bytes(&mut seed1, &mut buf1[..len]);
+            fill_bytes(&mut seed2, &mut buf2[..len]);
+            assert_eq!(buf1, buf2);
+            assert_eq!(seed1, seed2);
+
+            // Seed advanced once per started 8-byte chunk
+            let mut expected = 99u64;
+            let mut words = alloc::vec::Vec::new();
+            for _ in 0..len.div_ceil(8) {
+                words.extend_from_slice(&smc_rand(&mut expected).to_le_bytes());
+            }
+            assert_eq!(seed1, expected);
+            assert_eq!(buf1[..len], words[..len]);
+        }
+    }
+
+    #[test]
+    fn test_random_block() {
+        let mut seed1 = 7u64;
+        let mut seed2 = 7u64;
+        let block = random_block(&mut seed1);
+        assert_eq!(block, random_block(&mut seed2));
+
+        let mut seed3 = 7u64;
+        let mut buf = [0u8; 32];
+        fill_bytes(&mut seed3, &mut buf);
+        assert_eq!(block, buf);
+        assert_eq!(seed1, seed3);
+
+        let mut expected = 7u64;
+        for _ in 0..4 {
+            let _ = smc_rand(&mut expected);
+        }
+        assert_eq!(seed1, expected);
+    }
+}
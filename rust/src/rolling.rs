@@ -0,0 +1,145 @@
+//! Rolling hash over a fixed-size window.
+
+use crate::{mix, SMC_SECRET};
+
+/// Multiplier of the window polynomial (odd, so every power is invertible)
+const BASE: u64 = SMC_SECRET[4];
+
+/// Rabin-Karp style rolling hash.
+///
+/// The window size is fixed by [`new`](Self::new); [`roll`](Self::roll)
+/// slides it by one byte in O(1). Internally the window is a polynomial in
+/// `BASE` modulo 2^64, and [`get`](Self::get) finalizes it with [`mix`] so
+/// the returned value is well distributed. Rolling is exact: after any
+/// sequence of rolls, `get` returns the same value as `RollingHash::new` on
+/// the current window.
+///
+/// This is a different function from [`smchash`](fn@crate::smchash) and is
+/// meant for substring search, not as a general-purpose hash.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::RollingHash;
+///
+/// let text = b"the quick brown fox";
+/// let needle = RollingHash::new(b"brown").get();
+///
+/// let mut h = RollingHash::new(&text[..5]);
+/// let mut found = None;
+/// for i in 0..=text.len() - 5 {
+///     if i > 0 {
+///         h.roll(text[i - 1], text[i + 4]);
+///     }
+///     if h.get() == needle && &text[i..i + 5] == b"brown" {
+///         found = Some(i);
+///         break;
+///     }
+/// }
+/// assert_eq!(found, Some(10));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RollingHash {
+    state: u64,
+    /// `BASE^(window - 1)`, the weight of the byte leaving the window
+    out_weight: u64,
+    window: usize,
+}
+
+impl RollingHash {
+    /// Hash an initial window. Its length fixes the window size.
+    pub fn new(window: &[u8]) -> Self {
+        let mut state = 0u64;
+        let mut out_weight = 1u64;
+        for (i, &b) in window.iter().enumerate() {
+            state = state.wrapping_mul(BASE).wrapping_add(b as u64);
+            if i > 0 {
+                out_weight = out_weight.wrapping_mul(BASE);
+            }
+        }
+        RollingHash {
+            state,
+            out_weight,
+            window: window.len(),
+        }
+    }
+
+    /// Slide the window by one byte: `out_byte` leaves at the front and
+    /// `in_byte` enters at the back.
+    ///
+    /// `out_byte` must be the current first byte of the window; passing any
+    /// other byte leaves the hash inconsistent with the data.
+    #[inline]
+    pub fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        if self.window == 0 {
+            return;
+        }
+        self.state = self
+            .state
+            .wrapping_sub((out_byte as u64).wrapping_mul(self.out_weight))
+            .wrapping_mul(BASE)
+            .wrapping_add(in_byte as u64);
+    }
+
+    /// Hash of the current window.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> u64 {
+        mix(
+            self.state ^ SMC_SECRET[0],
+            SMC_SECRET[1] ^ self.window as u64,
+        )
+    }
+
+    /// Window size in bytes, fixed at construction.
+    #[inline]
+    #[must_use]
+    pub fn window(&self) -> usize {
+        self.window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_rolling_matches_fresh() {
+        let mut seed = 13u64;
+        let mut data = [0u8; 600];
+        crate::fill_bytes(&mut seed, &mut data);
+
+        for window in [1usize, 2, 7, 16, 64, 100] {
+            let mut h = RollingHash::new(&data[..window]);
+            assert_eq!(h.window(), window);
+            for i in 1..=data.len() - window {
+                h.roll(data[i - 1], data[i + window - 1]);
+                assert_eq!(
+                    h.get(),
+                    RollingHash::new(&data[i..i + window]).get(),
+                    "window {} offset {}",
+                    window,
+                    i
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_distinct() {
+        let words: [&[u8]; 6] = [b"abcd", b"abce", b"bcda", b"dcba", b"aaaa", b"\0\0\0\0"];
+        let hashes: Vec<u64> = words.iter().map(|w| RollingHash::new(w).get()).collect();
+        for i in 0..hashes.len() {
+            for j in 0..i {
+                assert_ne!(hashes[i], hashes[j], "{:?} {:?}", words[i], words[j]);
+            }
+        }
+        // Same bytes, different window size
+        assert_ne!(RollingHash::new(b"").get(), RollingHash::new(b"\0").get());
+        assert_ne!(
+            RollingHash::new(b"\0").get(),
+            RollingHash::new(b"\0\0").get()
+        );
+    }
+}
@@ -0,0 +1,423 @@
+//! Custom secret generation.
+
+use crate::{fill_bytes, smc_rand, smchash, smchash_secret_unchecked};
+
+/// Bytes with exactly 4 bits set, so every generated secret has 32 bits set
+const SECRET_BYTES: [u8; 70] = [
+    15, 23, 27, 29, 30, 39, 43, 45, 46, 51, 53, 54, 57, 58, 60, 71, 75, 77, 78, 83, 85, 86, 89, 90,
+    92, 99, 101, 102, 105, 106, 108, 113, 114, 116, 120, 135, 139, 141, 142, 147, 149, 150, 153,
+    154, 156, 163, 165, 166, 169, 170, 172, 177, 178, 180, 184, 195, 197, 198, 201, 202, 204, 209,
+    210, 212, 216, 225, 226, 228, 232, 240,
+];
+
+/// Generate a custom secret array from a seed.
+///
+/// The result satisfies every property required of smcHash secrets: each
+/// value is odd, has exactly 32 bits set, is prime, and differs from every
+/// other value by exactly 32 bits. Output is deterministic and matches the C
+/// implementation's `smc_make_secret()` for the same seed.
+///
+/// Generation is a rejection search and typically takes around a second, so
+/// generate secrets once at startup rather than per use.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use smchash::{smc_make_secret, smchash_secret};
+///
+/// let secret = smc_make_secret(0x1234);
+/// let hash = smchash_secret(b"data", 0, &secret);
+/// ```
+#[must_use]
+pub fn smc_make_secret(mut seed: u64) -> [u64; 9] {
+    let mut secret = [0u64; 9];
+
+    for i in 0..9 {
+        loop {
+            let mut s = 0u64;
+            for j in (0..64).step_by(8) {
+                let c = SECRET_BYTES[(smc_rand(&mut seed) % SECRET_BYTES.len() as u64) as usize];
+                s |= (c as u64) << j;
+            }
+
+            if s & 1 == 0 {
+                continue;
+            }
+            if secret[..i]
+                .iter()
+                .any(|&prev| (prev ^ s).count_ones() != 32)
+            {
+                continue;
+            }
+            if is_prime(s) {
+                secret[i] = s;
+                break;
+            }
+        }
+    }
+
+    secret
+}
+
+/// Derive a seed from arbitrary key material.
+///
+/// Hashes `material` with [`smchash`](fn@smchash), so human-readable names
+/// such as tenant IDs or configuration strings give well-distributed,
+/// reproducible seeds. Like the hash itself, the result is stable across
+/// runs and platforms.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{seed_from_bytes, smchash_seeded};
+///
+/// let tenant = seed_from_bytes(b"tenant-42");
+/// let h = smchash_seeded(b"user:1", tenant);
+/// assert_ne!(h, smchash_seeded(b"user:1", seed_from_bytes(b"tenant-43")));
+/// ```
+#[inline]
+#[must_use]
+pub fn seed_from_bytes(material: &[u8]) -> u64 {
+    smchash(material)
+}
+
+/// Reason a secret array was rejected by [`validate_secret`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SecretError {
+    /// `secret[index]` is even
+    NotOdd { index: usize },
+    /// `secret[index]` does not have exactly 32 bits set
+    WrongPopcount { index: usize, popcount: u32 },
+    /// `secret[index]` and `secret[other]` do not differ in exactly 32 bits
+    BadHammingDistance {
+        index: usize,
+        other: usize,
+        distance: u32,
+    },
+    /// `secret[index]` is not prime
+    NotPrime { index: usize },
+}
+
+impl SecretError {
+    /// Index of the offending secret value.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        match *self {
+            SecretError::NotOdd { index }
+            | SecretError::WrongPopcount { index, .. }
+            | SecretError::BadHammingDistance { index, .. }
+            | SecretError::NotPrime { index } => index,
+        }
+    }
+}
+
+impl core::fmt::Display for SecretError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            SecretError::NotOdd { index } => write!(f, "secret[{}] is not odd", index),
+            SecretError::WrongPopcount { index, popcount } => {
+                write!(
+                    f,
+                    "secret[{}] has {} bits set, expected 32",
+                    index, popcount
+                )
+            }
+            SecretError::BadHammingDistance {
+                index,
+                other,
+                distance,
+            } => write!(
+                f,
+                "secret[{}] differs from secret[{}] in {} bits, expected 32",
+                index, other, distance
+            ),
+            SecretError::NotPrime { index } => write!(f, "secret[{}] is not prime", index),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SecretError {}
+
+/// Check that a secret array has every property required of smcHash secrets.
+///
+/// Values are checked in order; for each one the cheap properties (odd, 32
+/// bits set, hamming distance 32 to every earlier value) are checked before
+/// primality. The first failure is returned.
+///
+/// Note that the crate's built-in default secret predates the primality
+/// requirement: only its last value is prime, so it does not pass this check.
+/// It is kept as-is because changing it would change every hash output.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{validate_secret, SecretError};
+///
+/// let mut secret = [
+///     0xf03aa5f0392b74c3, 0xa6ca3378b4cc6c35, 0xe1274b4b960f1ec5,
+///     0xe46cac0f4d8d5933, 0xd4b8478d3cd42bc9, 0xa3d24bc96335711b,
+///     0xb85a9a1e5578178d, 0x636596992ee8b499, 0x635cd16a99d18d4b,
+/// ];
+/// assert_eq!(validate_secret(&secret), Ok(()));
+///
+/// secret[3] ^= 1;
+/// assert_eq!(validate_secret(&secret), Err(SecretError::NotOdd { index: 3 }));
+/// ```
+pub fn validate_secret(secret: &[u64; 9]) -> Result<(), SecretError> {
+    for (index, &s) in secret.iter().enumerate() {
+        if s & 1 == 0 {
+            return Err(SecretError::NotOdd { index });
+        }
+        let popcount = s.count_ones();
+        if popcount != 32 {
+            return Err(SecretError::WrongPopcount { index, popcount });
+        }
+        for (other, &t) in secret[..index].iter().enumerate() {
+            let distance = (s ^ t).count_ones();
+            if distance != 32 {
+                return Err(SecretError::BadHammingDistance {
+                    index,
+                    other,
+                    distance,
+                });
+            }
+        }
+        if !is_prime(s) {
+            return Err(SecretError::NotPrime { index });
+        }
+    }
+    Ok(())
+}
+
+/// Length of the inputs used by [`avalanche_bias`]: one bulk block plus a
+/// 16-byte tail, so every secret value takes part
+const AVALANCHE_LEN: usize = 144;
+
+/// Measure how far a secret's avalanche behaviour is from ideal.
+///
+/// For each of the 1152 input bits of a 144-byte input (long enough to run
+/// the bulk loop, so all nine secret values are used), hashes `samples`
+/// pseudo-random inputs with [`smchash_secret`](crate::smchash_secret)
+/// with and without that bit flipped and records how often each of the 64
+/// output bits changes. Returns the largest deviation of any of those flip
+/// probabilities from 0.5. Inputs are generated from a fixed seed, so the
+/// result is deterministic. The secret is not passed through
+/// [`validate_secret`], so weak secrets can be measured in debug builds too.
+///
+/// The statistical noise floor is about `2.2 / sqrt(samples)`, and a good
+/// secret stays close to it. The cost is `1152 * samples` hashes of 144
+/// bytes, so a few thousand samples take well under a second.
+///
+/// # Panics
+///
+/// Panics if `samples` is zero.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use smchash::{avalanche_bias, smc_make_secret};
+///
+/// let secret = smc_make_secret(0x1234);
+/// assert!(avalanche_bias(&secret, 2000) < 0.07);
+/// ```
+#[must_use]
+pub fn avalanche_bias(secret: &[u64; 9], samples: usize) -> f64 {
+    assert!(samples != 0, "avalanche_bias: samples must be non-zero");
+
+    let mut worst = 0.0f64;
+    for bit in 0..AVALANCHE_LEN * 8 {
+        let mut seed = 0x5eed;
+        let mut flips = [0u32; 64];
+        let mut input = [0u8; AVALANCHE_LEN];
+        for _ in 0..samples {
+            fill_bytes(&mut seed, &mut input);
+            let h = smchash_secret_unchecked(&input, 0, secret);
+            input[bit / 8] ^= 1 << (bit % 8);
+            let diff = h ^ smchash_secret_unchecked(&input, 0, secret);
+            for (out, count) in flips.iter_mut().enumerate() {
+                *count += (diff >> out) as u32 & 1;
+            }
+        }
+        for &count in &flips {
+            let bias = (count as f64 / samples as f64 - 0.5).abs();
+            worst = worst.max(bias);
+        }
+    }
+    worst
+}
+
+/// `a * b mod n` without overflow
+#[inline]
+fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    ((a as u128 * b as u128) % n as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, n: u64) -> u64 {
+    let mut result = 1;
+    base %= n;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, n);
+        }
+        base = mul_mod(base, base, n);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Deterministic Miller-Rabin, exact for all 64-bit inputs
+pub(crate) fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for &p in &BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let mut d = n - 1;
+    let mut s = 0;
+    while d & 1 == 0 {
+        d >>= 1;
+        s += 1;
+    }
+
+    'witness: for &a in &BASES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SMC_SECRET;
+
+    /// Output of `smc_make_secret(0)`
+    const GOOD: [u64; 9] = [
+        0xf03aa5f0392b74c3,
+        0xa6ca3378b4cc6c35,
+        0xe1274b4b960f1ec5,
+        0xe46cac0f4d8d5933,
+        0xd4b8478d3cd42bc9,
+        0xa3d24bc96335711b,
+        0xb85a9a1e5578178d,
+        0x636596992ee8b499,
+        0x635cd16a99d18d4b,
+    ];
+
+    fn check(secret: &[u64; 9]) {
+        for (i, &s) in secret.iter().enumerate() {
+            assert_eq!(s & 1, 1, "secret[{}] is even", i);
+            assert_eq!(s.count_ones(), 32, "secret[{}] popcount", i);
+            assert!(is_prime(s), "secret[{}] is not prime", i);
+            for &t in &secret[..i] {
+                assert_eq!((s ^ t).count_ones(), 32, "secret[{}] hamming distance", i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes = [
+            2u64,
+            3,
+            5,
+            37,
+            41,
+            2053,
+            1_000_000_007,
+            18446744073709551557,
+        ];
+        let composites = [0u64, 1, 4, 9, 2047, 3215031751, 1_000_000_007 * 3, u64::MAX];
+        for &p in &primes {
+            assert!(is_prime(p), "{}", p);
+        }
+        for &c in &composites {
+            assert!(!is_prime(c), "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_make_secret() {
+        // Must match the C implementation's smc_make_secret(0, ...)
+        let secret = smc_make_secret(0);
+        assert_eq!(secret, GOOD);
+        check(&secret);
+
+        let other = smc_make_secret(12345);
+        check(&other);
+        assert_ne!(other, secret);
+    }
+
+    #[test]
+    fn test_avalanche_bias() {
+        assert!(avalanche_bias(&SMC_SECRET, 2000) < 0.07);
+        assert!(avalanche_bias(&GOOD, 2000) < 0.07);
+        assert_eq!(avalanche_bias(&GOOD, 50), avalanche_bias(&GOOD, 50));
+    }
+
+    #[test]
+    fn test_seed_from_bytes() {
+        // Pinned: the derivation must not change between runs or releases
+        assert_eq!(seed_from_bytes(b""), 0x76eee9b64c443120);
+        assert_eq!(seed_from_bytes(b"Hello, World!"), 0x25bb0982c5c0de6e);
+        assert_ne!(seed_from_bytes(b"tenant-42"), seed_from_bytes(b"tenant-43"));
+        assert_ne!(seed_from_bytes(b"a"), seed_from_bytes(b"A"));
+    }
+
+    #[test]
+    fn test_validate_secret() {
+        assert_eq!(validate_secret(&GOOD), Ok(()));
+
+        let mut s = GOOD;
+        s[3] ^= 1;
+        assert_eq!(validate_secret(&s), Err(SecretError::NotOdd { index: 3 }));
+
+        let mut s = GOOD;
+        s[0] |= 1 << 63 | 1 << 62 | 1 << 61 | 1 << 60 | 1 << 59;
+        assert!(matches!(
+            validate_secret(&s),
+            Err(SecretError::WrongPopcount { index: 0, .. })
+        ));
+
+        // Swapping a set and a clear bit keeps popcount but breaks the pairwise distance
+        let mut s = GOOD;
+        let set = (s[5] & !1).trailing_zeros();
+        let clear = (!s[5]).trailing_zeros();
+        s[5] ^= (1 << set) | (1 << clear);
+        assert!(matches!(
+            validate_secret(&s),
+            Err(SecretError::BadHammingDistance { index: 5, .. })
+        ));
+
+        // 2^32 - 1: odd with 32 bits set, but composite
+        let mut s = GOOD;
+        s[0] = 0xffff_ffff;
+        assert_eq!(validate_secret(&s), Err(SecretError::NotPrime { index: 0 }));
+
+        // The built-in default secret fails primality at its first value
+        assert_eq!(
+            validate_secret(&SMC_SECRET),
+            Err(SecretError::NotPrime { index: 0 })
+        );
+        assert_eq!(
+            alloc::format!("{}", SecretError::NotPrime { index: 3 }),
+            "secret[3] is not prime"
+        );
+    }
+}
@@ -0,0 +1,529 @@
+//! Hashing of [`serde::Serialize`] values (requires the `serde` feature).
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use serde::ser::{self, Serialize};
+
+use crate::SmcHasher;
+
+/// Hash any [`Serialize`] value.
+///
+/// Values are encoded into an internal binary form that is fed straight into
+/// [`SmcHasher`], so nothing is buffered. The encoding is prefix-free:
+///
+/// - every value starts with a one-byte type tag, so `1u8` and `1u32` differ
+/// - integers and floats follow as little-endian bytes; `bool` and `char` as
+///   one byte and a little-endian `u32`
+/// - strings, byte strings and all names (struct, field, variant) are
+///   prefixed with their length as a little-endian `u64`
+/// - sequences, tuples, maps and structs list their elements and close with
+///   an end tag that no value can start with
+///
+/// As a result `{"a": 1, "b": 2}` and `{"ab": 12}` cannot encode to the same
+/// bytes. Map entries are hashed in iteration order, so use ordered maps such
+/// as `BTreeMap` when the hash must be stable; `HashMap` order varies.
+///
+/// The result is deterministic across runs and platforms for values whose
+/// serialization is itself deterministic.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use smchash::hash_value;
+///
+/// let mut config = BTreeMap::new();
+/// config.insert("threads", 8);
+/// config.insert("retries", 3);
+/// let before = hash_value(&config, 0).unwrap();
+///
+/// config.insert("retries", 4);
+/// assert_ne!(hash_value(&config, 0).unwrap(), before);
+/// ```
+pub fn hash_value<T: Serialize + ?Sized>(value: &T, seed: u64) -> Result<u64, HashValueError> {
+    let mut encoder = Encoder {
+        hasher: SmcHasher::with_seed(seed),
+    };
+    value.serialize(&mut encoder)?;
+    Ok(encoder.hasher.finish())
+}
+
+/// Error returned by [`hash_value`] when the value's `Serialize` impl fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashValueError {
+    msg: String,
+}
+
+impl fmt::Display for HashValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl ser::StdError for HashValueError {}
+
+impl ser::Error for HashValueError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        HashValueError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+// Type tags. END closes containers and is never the first byte of a value.
+const END: u8 = 0;
+const BOOL: u8 = 1;
+const I8: u8 = 2;
+const I16: u8 = 3;
+const I32: u8 = 4;
+const I64: u8 = 5;
+const I128: u8 = 6;
+const U8: u8 = 7;
+const U16: u8 = 8;
+const U32: u8 = 9;
+const U64: u8 = 10;
+const U128: u8 = 11;
+const F32: u8 = 12;
+const F64: u8 = 13;
+const CHAR: u8 = 14;
+const STR: u8 = 15;
+const BYTES: u8 = 16;
+const NONE: u8 = 17;
+const SOME: u8 = 18;
+const UNIT: u8 = 19;
+const UNIT_STRUCT: u8 = 20;
+const UNIT_VARIANT: u8 = 21;
+const NEWTYPE_STRUCT: u8 = 22;
+const NEWTYPE_VARIANT: u8 = 23;
+const SEQ: u8 = 24;
+const TUPLE: u8 = 25;
+const TUPLE_STRUCT: u8 = 26;
+const TUPLE_VARIANT: u8 = 27;
+const MAP: u8 = 28;
+const STRUCT: u8 = 29;
+const STRUCT_VARIANT: u8 = 30;
+
+/// Serializer feeding the encoding into a hasher
+struct Encoder {
+    hasher: SmcHasher,
+}
+
+impl Encoder {
+    #[inline]
+    fn tag(&mut self, tag: u8) {
+        self.hasher.update(&[tag]);
+    }
+
+    /// Length-prefixed bytes
+    #[inline]
+    fn bytes(&mut self, v: &[u8]) {
+        self.hasher.update(&(v.len() as u64).to_le_bytes());
+        self.hasher.update(v);
+    }
+
+    #[inline]
+    fn tagged(&mut self, tag: u8, v: &[u8]) -> Result<(), HashValueError> {
+        self.tag(tag);
+        self.hasher.update(v);
+        Ok(())
+    }
+}
+
+impl ser::Serializer for &mut Encoder {
+    type Ok = ();
+    type Error = HashValueError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), HashValueError> {
+        self.tagged(BOOL, &[v as u8])
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), HashValueError> {
+        self.tagged(I8, &v.to_le_bytes())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), HashValueError> {
+        self.tagged(I16, &v.to_le_bytes())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), HashValueError> {
+        self.tagged(I32, &v.to_le_bytes())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), HashValueError> {
+        self.tagged(I64, &v.to_le_bytes())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), HashValueError> {
+        self.tagged(I128, &v.to_le_bytes())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), HashValueError> {
+        self.tagged(U8, &v.to_le_bytes())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), HashValueError> {
+        self.tagged(U16, &v.to_le_bytes())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), HashValueError> {
+        self.tagged(U32, &v.to_le_bytes())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), HashValueError> {
+        self.tagged(U64, &v.to_le_bytes())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), HashValueError> {
+        self.tagged(U128, &v.to_le_bytes())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), HashValueError> {
+        self.tagged(F32, &v.to_le_bytes())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), HashValueError> {
+        self.tagged(F64, &v.to_le_bytes())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), HashValueError> {
+        self.tagged(CHAR, &(v as u32).to_le_bytes())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), HashValueError> {
+        self.tag(STR);
+        self.bytes(v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), HashValueError> {
+        self.tag(BYTES);
+        self.bytes(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), HashValueError> {
+        self.tagged(NONE, &[])
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), HashValueError> {
+        self.tag(SOME);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), HashValueError> {
+        self.tagged(UNIT, &[])
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), HashValueError> {
+        self.tag(UNIT_STRUCT);
+        self.bytes(name.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), HashValueError> {
+        self.tag(UNIT_VARIANT);
+        self.bytes(name.as_bytes());
+        self.bytes(variant.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), HashValueError> {
+        self.tag(NEWTYPE_STRUCT);
+        self.bytes(name.as_bytes());
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), HashValueError> {
+        self.tag(NEWTYPE_VARIANT);
+        self.bytes(name.as_bytes());
+        self.bytes(variant.as_bytes());
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, HashValueError> {
+        self.tag(SEQ);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, HashValueError> {
+        self.tag(TUPLE);
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self, HashValueError> {
+        self.tag(TUPLE_STRUCT);
+        self.bytes(name.as_bytes());
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, HashValueError> {
+        self.tag(TUPLE_VARIANT);
+        self.bytes(name.as_bytes());
+        self.bytes(variant.as_bytes());
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, HashValueError> {
+        self.tag(MAP);
+        Ok(self)
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self, HashValueError> {
+        self.tag(STRUCT);
+        self.bytes(name.as_bytes());
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, HashValueError> {
+        self.tag(STRUCT_VARIANT);
+        self.bytes(name.as_bytes());
+        self.bytes(variant.as_bytes());
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut Encoder {
+    type Ok = ();
+    type Error = HashValueError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), HashValueError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashValueError> {
+        self.tagged(END, &[])
+    }
+}
+
+impl ser::SerializeTuple for &mut Encoder {
+    type Ok = ();
+    type Error = HashValueError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), HashValueError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashValueError> {
+        self.tagged(END, &[])
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut Encoder {
+    type Ok = ();
+    type Error = HashValueError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashValueError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashValueError> {
+        self.tagged(END, &[])
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut Encoder {
+    type Ok = ();
+    type Error = HashValueError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashValueError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashValueError> {
+        self.tagged(END, &[])
+    }
+}
+
+impl ser::SerializeMap for &mut Encoder {
+    type Ok = ();
+    type Error = HashValueError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), HashValueError> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashValueError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashValueError> {
+        self.tagged(END, &[])
+    }
+}
+
+impl ser::SerializeStruct for &mut Encoder {
+    type Ok = ();
+    type Error = HashValueError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), HashValueError> {
+        self.bytes(key.as_bytes());
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashValueError> {
+        self.tagged(END, &[])
+    }
+}
+
+impl ser::SerializeStructVariant for &mut Encoder {
+    type Ok = ();
+    type Error = HashValueError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), HashValueError> {
+        self.bytes(key.as_bytes());
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashValueError> {
+        self.tagged(END, &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Inner {
+        name: String,
+        tags: Vec<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct Outer {
+        id: u64,
+        inner: Inner,
+        parent: Option<Box<Outer>>,
+    }
+
+    fn outer(name: &str) -> Outer {
+        Outer {
+            id: 1,
+            inner: Inner {
+                name: name.to_string(),
+                tags: vec![1, 2, 3],
+            },
+            parent: Some(Box::new(Outer {
+                id: 0,
+                inner: Inner {
+                    name: String::new(),
+                    tags: vec![],
+                },
+                parent: None,
+            })),
+        }
+    }
+
+    #[test]
+    fn test_hash_value_nested() {
+        let a = hash_value(&outer("x"), 0).unwrap();
+        assert_eq!(a, hash_value(&outer("x"), 0).unwrap());
+        assert_ne!(a, hash_value(&outer("y"), 0).unwrap());
+        assert_ne!(a, hash_value(&outer("x"), 1).unwrap());
+    }
+
+    #[test]
+    fn test_hash_value_unambiguous() {
+        let mut ab = BTreeMap::new();
+        ab.insert("a", 1);
+        ab.insert("b", 2);
+        let mut joined = BTreeMap::new();
+        joined.insert("ab", 12);
+        assert_ne!(hash_value(&ab, 0).unwrap(), hash_value(&joined, 0).unwrap());
+
+        // Element boundaries and types are part of the encoding
+        fn h<T: Serialize + ?Sized>(value: &T) -> u64 {
+            hash_value(value, 0).unwrap()
+        }
+        assert_ne!(h(&("ab", "c")), h(&("a", "bc")));
+        assert_ne!(h(&vec![vec![1u8], vec![]]), h(&vec![vec![], vec![1u8]]));
+        assert_ne!(h(&1u8), h(&1u32));
+        assert_ne!(h(&Some(())), h(&()));
+    }
+
+    #[test]
+    fn test_hash_value_map_order() {
+        let mut forward = BTreeMap::new();
+        let mut backward = BTreeMap::new();
+        for i in 0..100 {
+            forward.insert(i, i * 2);
+        }
+        for i in (0..100).rev() {
+            backward.insert(i, i * 2);
+        }
+        assert_eq!(
+            hash_value(&forward, 0).unwrap(),
+            hash_value(&backward, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hash_value_error() {
+        struct Failing;
+        impl Serialize for Failing {
+            fn serialize<S: ser::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(ser::Error::custom("nope"))
+            }
+        }
+        let err = hash_value(&Failing, 0).unwrap_err();
+        assert_eq!(alloc::format!("{}", err), "nope");
+    }
+}
@@ -0,0 +1,117 @@
+//! Collision diagnostics over a key corpus (requires the `alloc` feature).
+
+use alloc::vec::Vec;
+
+use crate::{smchash, smchash32};
+
+/// Result of [`collision_stats`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CollisionReport {
+    /// Number of keys hashed
+    pub keys: usize,
+    /// Number of distinct truncated hashes
+    pub distinct: usize,
+    /// Number of key pairs with equal truncated hashes
+    pub collision_pairs: u64,
+    /// Expected number of colliding pairs for a uniformly random function
+    pub expected_pairs: f64,
+}
+
+/// Hash a corpus and count collisions at a given output width.
+///
+/// Each key is hashed with [`smchash32`] when `bits <= 32` and with
+/// [`smchash`](fn@smchash) otherwise, and the hash is truncated to its low
+/// `bits` bits. Compare `collision_pairs` with `expected_pairs`, which is
+/// `n * (n - 1) / 2 / 2^bits` for `n` keys: a count well above it means the
+/// corpus collides more than a random function would. Duplicate keys always
+/// collide, so deduplicate the corpus first.
+///
+/// # Panics
+///
+/// Panics if `bits` is 0 or greater than 64.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::collision_stats;
+///
+/// let paths: Vec<String> = (0..10_000).map(|i| format!("/var/log/app/{}.log", i)).collect();
+/// let keys: Vec<&[u8]> = paths.iter().map(|p| p.as_bytes()).collect();
+/// let report = collision_stats(&keys, 32);
+/// // A random 32-bit function gives about 0.01 colliding pairs here
+/// assert!(report.expected_pairs < 0.02);
+/// assert!(report.collision_pairs <= 1);
+/// ```
+#[must_use]
+pub fn collision_stats(keys: &[&[u8]], bits: u32) -> CollisionReport {
+    assert!(
+        (1..=64).contains(&bits),
+        "collision_stats: bits must be in 1..=64"
+    );
+    let mask = u64::MAX >> (64 - bits);
+    let mut hashes: Vec<u64> = keys
+        .iter()
+        .map(|k| {
+            let h = if bits <= 32 {
+                smchash32(k) as u64
+            } else {
+                smchash(k)
+            };
+            h & mask
+        })
+        .collect();
+    hashes.sort_unstable();
+
+    let mut distinct = 0;
+    let mut collision_pairs = 0u64;
+    for run in hashes.chunk_by(|a, b| a == b) {
+        let m = run.len() as u64;
+        distinct += 1;
+        collision_pairs += m * (m - 1) / 2;
+    }
+
+    let n = keys.len() as f64;
+    CollisionReport {
+        keys: keys.len(),
+        distinct,
+        collision_pairs,
+        expected_pairs: n * (n - 1.0) / 2.0 / (1u128 << bits) as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::string::String;
+
+    #[test]
+    fn test_collision_stats() {
+        let names: Vec<String> = (0..20_000).map(|i| format!("/usr/lib/{}.so", i)).collect();
+        let keys: Vec<&[u8]> = names.iter().map(|n| n.as_bytes()).collect();
+
+        let wide = collision_stats(&keys, 64);
+        assert_eq!(wide.keys, 20_000);
+        assert_eq!(wide.distinct, 20_000);
+        assert_eq!(wide.collision_pairs, 0);
+
+        // 20k keys in 2^16 slots: about 3050 colliding pairs expected
+        let narrow = collision_stats(&keys, 16);
+        assert!((narrow.expected_pairs - 3051.6).abs() < 0.1);
+        let ratio = narrow.collision_pairs as f64 / narrow.expected_pairs;
+        assert!((0.85..1.15).contains(&ratio), "ratio {}", ratio);
+        assert!(narrow.distinct < 20_000);
+
+        // Duplicates always collide
+        let dup: [&[u8]; 4] = [b"a", b"a", b"a", b"b"];
+        let report = collision_stats(&dup, 64);
+        assert_eq!(report.distinct, 2);
+        assert_eq!(report.collision_pairs, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_collision_stats_zero_bits() {
+        let _ = collision_stats(&[b"a"], 0);
+    }
+}
@@ -0,0 +1,584 @@
+//! Incremental (streaming) hashing.
+//!
+//! [`SmcHasher`] produces the same value as [`smchash_seeded`](crate::smchash_seeded)
+//! on the concatenation of everything passed to [`SmcHasher::update`], no matter
+//! how the input is split into chunks.
+
+use crate::bulk::{accumulate, fold};
+use crate::{mix, mum, read64, smchash_secret_unchecked, smchash_seeded, tail, SMC_SECRET};
+
+/// Bulk block size: 8 lanes x 16 bytes
+const BLOCK: usize = 128;
+
+/// Streaming smcHash state.
+///
+/// Bytes are buffered until a full 128-byte block is known not to be the last
+/// block of the input, at which point it is folded into the 8 bulk lanes. The
+/// final 1..=128 bytes always stay in the buffer so [`finish`](Self::finish)
+/// can run the same tail logic as the one-shot function.
+///
+/// Cloning is cheap (a few hundred bytes, no allocation) and snapshots the
+/// state: a clone taken after a shared prefix continues independently and
+/// hashes exactly the prefix followed by whatever it is fed afterwards.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash, SmcHasher};
+///
+/// let mut hasher = SmcHasher::new();
+/// hasher.update(b"Hello, ");
+/// hasher.update(b"World!");
+/// assert_eq!(hasher.finish(), smchash(b"Hello, World!"));
+/// ```
+#[derive(Clone)]
+pub struct SmcHasher {
+    seed: u64,
+    lanes: [u64; 8],
+    buf: [u8; BLOCK],
+    buf_len: usize,
+    /// Last 16 bytes of the most recently consumed block
+    tail: [u8; 16],
+    total: u64,
+    secret: [u64; 9],
+    /// Built with a custom secret: finish like `smchash_secret`
+    keyed: bool,
+}
+
+impl SmcHasher {
+    /// Create a hasher using the default seed (same as [`smchash`](fn@crate::smchash)).
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_seed(SMC_SECRET[0])
+    }
+
+    /// Create a hasher with a custom seed (same as [`smchash_seeded`]).
+    pub fn with_seed(seed: u64) -> Self {
+        let lane = seed ^ mix(seed ^ SMC_SECRET[2], SMC_SECRET[1]);
+        Self::init(seed, lane, SMC_SECRET, false)
+    }
+
+    /// Create a hasher with custom secrets (same as [`smchash_secret`](crate::smchash_secret)).
+    fn with_secret(seed: u64, secret: [u64; 9]) -> Self {
+        // smchash_secret derives the long-input seed from secret[0]
+        let lane = seed ^ mix(seed ^ secret[0], secret[1]);
+        Self::init(seed, lane, secret, true)
+    }
+
+    fn init(seed: u64, lane: u64, secret: [u64; 9], keyed: bool) -> Self {
+        SmcHasher {
+            seed,
+            lanes: [lane; 8],
+            buf: [0; BLOCK],
+            buf_len: 0,
+            tail: [0; 16],
+            total: 0,
+            secret,
+            keyed,
+        }
+    }
+
+    /// Feed more data into the hasher.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.total += data.len() as u64;
+
+        if self.buf_len + data.len() <= BLOCK {
+            self.buf[self.buf_len..self.buf_len + data.len()].copy_from_slice(data);
+            self.buf_len += data.len();
+            return;
+        }
+
+        // More input follows the buffered bytes, so a full buffer is a bulk block
+        if self.buf_len > 0 {
+            let fill = BLOCK - self.buf_len;
+            self.buf[self.buf_len..].copy_from_slice(&data[..fill]);
+            data = &data[fill..];
+            accumulate(&mut self.lanes, &self.buf, &self.secret);
+            self.tail.copy_from_slice(&self.buf[BLOCK - 16..]);
+            self.buf_len = 0;
+        }
+
+        // Keep at least one byte back: the last block goes through the tail path
+        while data.len() > BLOCK {
+            accumulate(&mut self.lanes, &data[..BLOCK], &self.secret);
+            self.tail.copy_from_slice(&data[BLOCK - 16..BLOCK]);
+            data = &data[BLOCK..];
+        }
+
+        self.buf[..data.len()].copy_from_slice(data);
+        self.buf_len = data.len();
+    }
+
+    /// Feed the low `n` little-endian bytes of `v`, same as `update`.
+    ///
+    /// Writes straight into the buffer when it has room, which is the common
+    /// case for `Hasher::write_u64` and friends.
+    #[inline]
+    pub(crate) fn update_word(&mut self, v: u64, n: usize) {
+        debug_assert!(n <= 8);
+        if self.buf_len + n <= BLOCK {
+            self.buf[self.buf_len..self.buf_len + n].copy_from_slice(&v.to_le_bytes()[..n]);
+            self.buf_len += n;
+            self.total += n as u64;
+        } else {
+            self.update(&v.to_le_bytes()[..n]);
+        }
+    }
+
+    /// Return the hash of all data fed so far.
+    ///
+    /// The hasher is not consumed; more data may be added afterwards.
+    #[must_use]
+    pub fn finish(&self) -> u64 {
+        if self.total <= BLOCK as u64 {
+            let data = &self.buf[..self.buf_len];
+            return if self.keyed {
+                smchash_secret_unchecked(data, self.seed, &self.secret)
+            } else {
+                smchash_seeded(data, self.seed)
+            };
+        }
+
+        let secret = &self.secret;
+        let len = self.total;
+        let seed = tail(&self.buf[..self.buf_len], fold(&self.lanes), secret);
+
+        // The last 16 bytes of input may reach back into the previous block
+        let mut last = [0u8; 16];
+        let n = self.buf_len;
+        if n >= 16 {
+            last.copy_from_slice(&self.buf[n - 16..n]);
+        } else {
+            last[..16 - n].copy_from_slice(&self.tail[n..]);
+            last[16 - n..].copy_from_slice(&self.buf[..n]);
+        }
+
+        let mut a = read64(&last) ^ len ^ secret[1];
+        let mut b = read64(&last[8..]) ^ seed;
+        mum(&mut a, &mut b);
+        mix(a ^ secret[8], b ^ secret[1] ^ len)
+    }
+}
+
+impl Default for SmcHasher {
+    /// Same as [`SmcHasher::new`]: the default seed and secret.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for an [`SmcHasher`] with a custom seed and/or secret.
+///
+/// Without a custom secret the built hasher matches
+/// [`smchash_seeded`](crate::smchash_seeded); with one it matches
+/// [`smchash_secret`](crate::smchash_secret). The seed defaults to the seed
+/// used by [`smchash`](fn@crate::smchash).
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_secret, SmcHasherBuilder};
+/// # let secret = [
+/// #     0xf03aa5f0392b74c3, 0xa6ca3378b4cc6c35, 0xe1274b4b960f1ec5,
+/// #     0xe46cac0f4d8d5933, 0xd4b8478d3cd42bc9, 0xa3d24bc96335711b,
+/// #     0xb85a9a1e5578178d, 0x636596992ee8b499, 0x635cd16a99d18d4b,
+/// # ];
+///
+/// let mut hasher = SmcHasherBuilder::new().seed(7).secret(secret).build();
+/// hasher.update(b"data");
+/// assert_eq!(hasher.finish(), smchash_secret(b"data", 7, &secret));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmcHasherBuilder {
+    seed: Option<u64>,
+    secret: Option<[u64; 9]>,
+}
+
+impl SmcHasherBuilder {
+    /// Start with the default seed and secret.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the seed.
+    #[inline]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Use custom secrets instead of the built-in ones.
+    ///
+    /// Like [`smchash_secret`](crate::smchash_secret), debug builds panic if
+    /// `secret` fails [`validate_secret`](crate::validate_secret).
+    #[inline]
+    pub fn secret(mut self, secret: [u64; 9]) -> Self {
+        debug_assert!(
+            secret == SMC_SECRET || crate::validate_secret(&secret).is_ok(),
+            "SmcHasherBuilder: secret fails validate_secret"
+        );
+        self.secret = Some(secret);
+        self
+    }
+
+    /// Create the hasher.
+    pub fn build(self) -> SmcHasher {
+        let seed = self.seed.unwrap_or(SMC_SECRET[0]);
+        match self.secret {
+            Some(secret) => SmcHasher::with_secret(seed, secret),
+            None => SmcHasher::with_seed(seed),
+        }
+    }
+}
+
+/// Hash several slices as if they were one concatenated buffer.
+///
+/// Returns the same value as [`smchash_seeded`] on the concatenation of
+/// `parts`, without allocating. Empty parts have no effect.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_multi, smchash_seeded};
+///
+/// let h = smchash_multi(&[b"user:", b"42", b":profile"], 7);
+/// assert_eq!(h, smchash_seeded(b"user:42:profile", 7));
+/// ```
+#[must_use]
+pub fn smchash_multi(parts: &[&[u8]], seed: u64) -> u64 {
+    smchash_chunks(parts.iter().copied(), seed)
+}
+
+/// Hash a lazily produced sequence of chunks as one concatenated buffer.
+///
+/// Like [`smchash_multi`], but takes any iterator of borrowed chunks, so
+/// they never need collecting into a slice. Returns the same value as
+/// [`smchash_seeded`] on the concatenation; empty chunks and chunk
+/// boundaries inside a 128-byte block have no effect.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_chunks, smchash_seeded};
+///
+/// let frames = "GET /index.html HTTP/1.1".split_inclusive(' ').map(str::as_bytes);
+/// assert_eq!(smchash_chunks(frames, 7), smchash_seeded(b"GET /index.html HTTP/1.1", 7));
+/// ```
+#[must_use]
+pub fn smchash_chunks<'a, I: IntoIterator<Item = &'a [u8]>>(chunks: I, seed: u64) -> u64 {
+    let mut hasher = SmcHasher::with_seed(seed);
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    hasher.finish()
+}
+
+/// Hash the bytes yielded by an iterator.
+///
+/// Returns the same value as [`smchash`](fn@crate::smchash) on the collected
+/// bytes, without allocating: bytes are gathered into a 128-byte stack
+/// buffer and fed to [`SmcHasher`] a block at a time.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash, smchash_iter};
+///
+/// let upper = smchash_iter(b"hello".iter().map(|b| b.to_ascii_uppercase()));
+/// assert_eq!(upper, smchash(b"HELLO"));
+/// ```
+#[must_use]
+pub fn smchash_iter<I: IntoIterator<Item = u8>>(iter: I) -> u64 {
+    let mut hasher = SmcHasher::new();
+    let mut chunk = [0u8; BLOCK];
+    let mut n = 0;
+    for byte in iter {
+        chunk[n] = byte;
+        n += 1;
+        if n == BLOCK {
+            hasher.update(&chunk);
+            n = 0;
+        }
+    }
+    hasher.update(&chunk[..n]);
+    hasher.finish()
+}
+
+/// Hash ASCII case-insensitively.
+///
+/// Returns the same value as [`smchash_seeded`] on `data` with ASCII `A-Z`
+/// folded to `a-z`; every other byte, including non-ASCII UTF-8, is hashed
+/// unchanged. Folding happens block by block on the stack, so nothing is
+/// allocated.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_ascii_ci, smchash_seeded};
+///
+/// let h = smchash_ascii_ci(b"Content-Type", 0);
+/// assert_eq!(h, smchash_ascii_ci(b"content-type", 0));
+/// assert_eq!(h, smchash_seeded(b"content-type", 0));
+/// ```
+#[must_use]
+pub fn smchash_ascii_ci(data: &[u8], seed: u64) -> u64 {
+    let mut hasher = SmcHasher::with_seed(seed);
+    let mut chunk = [0u8; BLOCK];
+    for part in data.chunks(BLOCK) {
+        let lower = &mut chunk[..part.len()];
+        lower.copy_from_slice(part);
+        lower.make_ascii_lowercase();
+        hasher.update(lower);
+    }
+    hasher.finish()
+}
+
+/// Resumable hash state returned by [`smchash_prefix`].
+///
+/// Opaque and cheap to clone; resume it as often as needed with
+/// [`smchash_resume`].
+#[derive(Clone)]
+pub struct SmcState {
+    hasher: SmcHasher,
+}
+
+/// Hash a prefix once and keep the state for later extension.
+///
+/// `smchash_resume(&smchash_prefix(header, seed), rest)` equals
+/// [`smchash_seeded`] on `header` followed by `rest`, for any split point, so
+/// a fixed header only needs to be hashed once.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_prefix, smchash_resume, smchash_seeded};
+///
+/// let header = smchash_prefix(b"POST /api/v1/items HTTP/1.1\r\n", 0);
+/// for body in [&b"{\"id\":1}"[..], b"{\"id\":2}"] {
+///     let full = [&b"POST /api/v1/items HTTP/1.1\r\n"[..], body].concat();
+///     assert_eq!(smchash_resume(&header, body), smchash_seeded(&full, 0));
+/// }
+/// ```
+#[must_use]
+pub fn smchash_prefix(data: &[u8], seed: u64) -> SmcState {
+    let mut hasher = SmcHasher::with_seed(seed);
+    hasher.update(data);
+    SmcState { hasher }
+}
+
+/// Finish a [`smchash_prefix`] state as if `rest` were appended to the prefix.
+///
+/// The state itself is not modified and can be resumed again.
+#[must_use]
+pub fn smchash_resume(state: &SmcState, rest: &[u8]) -> u64 {
+    let mut hasher = state.hasher.clone();
+    hasher.update(rest);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{smchash, smchash_secret};
+
+    fn data(len: usize) -> alloc::vec::Vec<u8> {
+        (0..len).map(|i| (i * 31 + 7) as u8).collect()
+    }
+
+    fn chunked(data: &[u8], chunk: usize, seed: u64) -> u64 {
+        let mut hasher = SmcHasher::with_seed(seed);
+        for c in data.chunks(chunk) {
+            hasher.update(c);
+        }
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_streaming_chunks() {
+        for len in 0..700 {
+            let d = data(len);
+            let expected = smchash(&d);
+            for &chunk in &[1, 7, 127] {
+                assert_eq!(
+                    chunked(&d, chunk, SMC_SECRET[0]),
+                    expected,
+                    "len {} chunk {}",
+                    len,
+                    chunk
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_seeded() {
+        let d = data(1000);
+        for &chunk in &[1, 16, 128, 129, 1000] {
+            assert_eq!(chunked(&d, chunk, 12345), smchash_seeded(&d, 12345));
+        }
+    }
+
+    #[test]
+    fn test_streaming_finish_midway() {
+        let d = data(300);
+        let mut hasher = SmcHasher::new();
+        hasher.update(&d[..150]);
+        assert_eq!(hasher.finish(), smchash(&d[..150]));
+        hasher.update(&d[150..]);
+        assert_eq!(hasher.finish(), smchash(&d));
+    }
+
+    #[test]
+    fn test_default_and_builder() {
+        let d = data(400);
+        let mut hasher = SmcHasher::default();
+        hasher.update(&d);
+        assert_eq!(hasher.finish(), smchash(&d));
+
+        let mut built = SmcHasherBuilder::new().seed(5).build();
+        built.update(&d);
+        assert_eq!(built.finish(), smchash_seeded(&d, 5));
+
+        // smc_make_secret(0)
+        let secret = [
+            0xf03aa5f0392b74c3,
+            0xa6ca3378b4cc6c35,
+            0xe1274b4b960f1ec5,
+            0xe46cac0f4d8d5933,
+            0xd4b8478d3cd42bc9,
+            0xa3d24bc96335711b,
+            0xb85a9a1e5578178d,
+            0x636596992ee8b499,
+            0x635cd16a99d18d4b,
+        ];
+        for len in [0, 5, 16, 17, 100, 128, 129, 256, 257, 400] {
+            for &chunk in &[1, 31, 400] {
+                let mut h = SmcHasherBuilder::new().seed(5).secret(secret).build();
+                for c in d[..len].chunks(chunk) {
+                    h.update(c);
+                }
+                assert_eq!(
+                    h.finish(),
+                    smchash_secret(&d[..len], 5, &secret),
+                    "len {}",
+                    len
+                );
+            }
+        }
+        let mut h = SmcHasherBuilder::new().secret(secret).build();
+        h.update(&d);
+        assert_eq!(h.finish(), smchash_secret(&d, SMC_SECRET[0], &secret));
+    }
+
+    #[test]
+    fn test_clone_branches() {
+        let d = data(500);
+        for prefix in [0, 10, 128, 129, 300] {
+            let mut base = SmcHasher::new();
+            base.update(&d[..prefix]);
+
+            let mut left = base.clone();
+            let mut right = base.clone();
+            left.update(b"left suffix");
+            right.update(&d[prefix..]);
+
+            let mut expected = d[..prefix].to_vec();
+            expected.extend_from_slice(b"left suffix");
+            assert_eq!(left.finish(), smchash(&expected), "prefix {}", prefix);
+            assert_eq!(right.finish(), smchash(&d), "prefix {}", prefix);
+            assert_eq!(base.finish(), smchash(&d[..prefix]));
+        }
+    }
+
+    #[test]
+    fn test_iter() {
+        let bytes: alloc::vec::Vec<u8> = (0u8..200).collect();
+        assert_eq!(smchash_iter(0u8..200), smchash(&bytes));
+        for len in [0, 1, 127, 128, 129, 256, 257, 600] {
+            let d = data(len);
+            assert_eq!(smchash_iter(d.iter().copied()), smchash(&d), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_ascii_ci() {
+        assert_eq!(
+            smchash_ascii_ci(b"Content-Type", 3),
+            smchash_ascii_ci(b"CONTENT-TYPE", 3)
+        );
+        assert_eq!(
+            smchash_ascii_ci(b"Content-Type", 3),
+            smchash_seeded(b"content-type", 3)
+        );
+        // Non-ASCII bytes pass through: "Ä" is not folded to "ä"
+        assert_eq!(
+            smchash_ascii_ci("ÄB".as_bytes(), 3),
+            smchash_seeded("Äb".as_bytes(), 3)
+        );
+        assert_ne!(
+            smchash_ascii_ci("ÄB".as_bytes(), 3),
+            smchash_seeded("äb".as_bytes(), 3)
+        );
+
+        let d = data(600);
+        let lower = d.to_ascii_lowercase();
+        for len in [0, 5, 16, 17, 128, 129, 300, 600] {
+            assert_eq!(
+                smchash_ascii_ci(&d[..len], 3),
+                smchash_seeded(&lower[..len], 3),
+                "len {}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn test_prefix_resume() {
+        let d = data(600);
+        for split in [0, 1, 15, 16, 17, 127, 128, 129, 200, 256, 257, 600] {
+            let state = smchash_prefix(&d[..split], 8);
+            for end in [split, split + 1, split + 130, 600] {
+                let end = end.min(600);
+                assert_eq!(
+                    smchash_resume(&state, &d[split..end]),
+                    smchash_seeded(&d[..end], 8),
+                    "split {} end {}",
+                    split,
+                    end
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_chunks_random_splits() {
+        let d = data(1000);
+        let mut rng = 77u64;
+        for _ in 0..200 {
+            let len = crate::smc_rand_range(&mut rng, 1001) as usize;
+            let mut cuts: alloc::vec::Vec<usize> = (0..crate::smc_rand_range(&mut rng, 12))
+                .map(|_| crate::smc_rand_range(&mut rng, len as u64 + 1) as usize)
+                .collect();
+            cuts.push(0);
+            cuts.push(len);
+            cuts.sort_unstable();
+            // Repeated cuts produce empty chunks
+            let chunks = cuts.windows(2).map(|w| &d[w[0]..w[1]]);
+            assert_eq!(smchash_chunks(chunks, 4), smchash_seeded(&d[..len], 4));
+        }
+    }
+
+    #[test]
+    fn test_multi() {
+        let d = data(400);
+        for split in [0, 1, 16, 100, 128, 129, 255, 400] {
+            let (x, y) = d.split_at(split);
+            assert_eq!(smchash_multi(&[x, y], 3), smchash_seeded(&d, 3));
+            assert_eq!(smchash_multi(&[x, b"", b"", y], 3), smchash_seeded(&d, 3));
+        }
+        assert_eq!(smchash_multi(&[], 3), smchash_seeded(b"", 3));
+        assert_eq!(smchash_multi(&[b"", b""], 3), smchash_seeded(b"", 3));
+    }
+}
@@ -0,0 +1,147 @@
+//! Chunked tree hashing.
+
+use crate::{mix, smchash_seeded, SMC_SECRET};
+
+/// Hash `data` as independent fixed-size chunks combined into one value.
+///
+/// Unlike [`smchash_seeded`], every chunk can be hashed independently, so the
+/// work parallelizes across threads (see the `rayon` feature). The result
+/// depends only on `data`, `chunk` and `seed`, never on how the work was
+/// scheduled. It is a different function from [`smchash_seeded`]: the same
+/// input gives a different value, and so does a different `chunk`.
+///
+/// The combination is fixed as follows, with `mix` from
+/// [`primitives`](crate::primitives) and `n` chunks `c[0..n]` as produced by
+/// `data.chunks(chunk)` (the last may be short; empty input has no chunks):
+///
+/// ```text
+/// leaf[i] = smchash_seeded(c[i], seed ^ mix(i ^ 0x2b55934dc35c9655, seed ^ 0x746ae48ed4d41e4d))
+/// acc     = seed
+/// acc     = mix(acc ^ leaf[i], 0xa3d8c38e78aaa6a9)      for i in 0..n, in order
+/// result  = mix(acc ^ data.len() ^ 0x71a5b853b43ca68b, chunk ^ 0x1bca69c565658bc3)
+/// ```
+///
+/// # Panics
+///
+/// Panics if `chunk` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::smchash_tree;
+///
+/// let data = vec![0u8; 1 << 20];
+/// let hash = smchash_tree(&data, 64 * 1024, 0);
+/// assert_eq!(hash, smchash_tree(&data, 64 * 1024, 0));
+/// ```
+#[must_use]
+pub fn smchash_tree(data: &[u8], chunk: usize, seed: u64) -> u64 {
+    assert!(chunk != 0, "smchash_tree: chunk must be non-zero");
+
+    #[cfg(feature = "rayon")]
+    let acc = {
+        use rayon::prelude::*;
+        let leaves: alloc::vec::Vec<u64> = data
+            .par_chunks(chunk)
+            .enumerate()
+            .map(|(i, c)| leaf(c, i, seed))
+            .collect();
+        leaves.into_iter().fold(seed, combine_leaf)
+    };
+
+    #[cfg(not(feature = "rayon"))]
+    let acc = data
+        .chunks(chunk)
+        .enumerate()
+        .map(|(i, c)| leaf(c, i, seed))
+        .fold(seed, combine_leaf);
+
+    mix(
+        acc ^ data.len() as u64 ^ SMC_SECRET[4],
+        chunk as u64 ^ SMC_SECRET[8],
+    )
+}
+
+/// Hash of chunk `i`
+#[inline]
+fn leaf(c: &[u8], i: usize, seed: u64) -> u64 {
+    smchash_seeded(
+        c,
+        seed ^ mix(i as u64 ^ SMC_SECRET[5], seed ^ SMC_SECRET[6]),
+    )
+}
+
+/// Fold the next leaf into the accumulator
+#[inline]
+fn combine_leaf(acc: u64, leaf: u64) -> u64 {
+    mix(acc ^ leaf, SMC_SECRET[7])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives;
+    use alloc::vec::Vec;
+
+    /// Single-threaded transcription of the documented combination
+    fn reference(data: &[u8], chunk: usize, seed: u64) -> u64 {
+        let mut acc = seed;
+        for (i, c) in data.chunks(chunk).enumerate() {
+            let leaf_seed =
+                seed ^ primitives::mix(i as u64 ^ 0x2b55934dc35c9655, seed ^ 0x746ae48ed4d41e4d);
+            acc = primitives::mix(acc ^ smchash_seeded(c, leaf_seed), 0xa3d8c38e78aaa6a9);
+        }
+        primitives::mix(
+            acc ^ data.len() as u64 ^ 0x71a5b853b43ca68b,
+            chunk as u64 ^ 0x1bca69c565658bc3,
+        )
+    }
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 29 + 1) as u8).collect()
+    }
+
+    #[test]
+    fn test_tree_matches_reference() {
+        let d = data(100_000);
+        for &len in &[0, 1, 1000, 4096, 100_000] {
+            for &chunk in &[1, 17, 1024, 4096, 1 << 20] {
+                assert_eq!(
+                    smchash_tree(&d[..len], chunk, 9),
+                    reference(&d[..len], chunk, 9),
+                    "len {} chunk {}",
+                    len,
+                    chunk
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tree_distinguishes_chunk_and_seed() {
+        let d = data(10_000);
+        assert_ne!(smchash_tree(&d, 1024, 0), smchash_tree(&d, 2048, 0));
+        assert_ne!(smchash_tree(&d, 1024, 0), smchash_tree(&d, 1024, 1));
+        assert_ne!(smchash_tree(b"", 1024, 0), smchash_tree(b"", 2048, 0));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_tree_thread_count_independent() {
+        let d = data(1 << 20);
+        let expected = reference(&d, 4096, 3);
+        for threads in [1, 2, 7] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            assert_eq!(pool.install(|| smchash_tree(&d, 4096, 3)), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tree_zero_chunk() {
+        let _ = smchash_tree(b"data", 0, 0);
+    }
+}
@@ -0,0 +1,128 @@
+//! Typed hash values with hex formatting.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{smchash, SmcError};
+
+/// A 64-bit hash value.
+///
+/// Formats as 16 zero-padded lowercase hex digits with `{}`, and supports
+/// `{:x}` and `{:X}` with the usual flags. Compares equal to the raw `u64`.
+/// Parses back from exactly 16 hex digits (either case) with [`str::parse`].
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash, smchash_typed, Hash64};
+///
+/// let h = smchash_typed(b"hello");
+/// assert_eq!(h, smchash(b"hello"));
+/// assert_eq!(Hash64(1).to_string(), "0000000000000001");
+/// assert_eq!(format!("{:X}", Hash64(0xabc)), "ABC");
+/// assert_eq!(h.to_string().parse::<Hash64>(), Ok(h));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hash64(pub u64);
+
+/// A 128-bit hash value.
+///
+/// Formats as 32 zero-padded lowercase hex digits with `{}`, and supports
+/// `{:x}` and `{:X}` with the usual flags. Compares equal to the raw `u128`.
+/// Parses back from exactly 32 hex digits (either case) with [`str::parse`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hash128(pub u128);
+
+/// Hash with the default seed, returning a [`Hash64`].
+///
+/// Same value as [`smchash`](fn@smchash).
+#[inline]
+#[must_use]
+pub fn smchash_typed(data: &[u8]) -> Hash64 {
+    Hash64(smchash(data))
+}
+
+macro_rules! impl_hash_type {
+    ($name:ident, $int:ty, $width:literal) => {
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!("{:0", $width, "x}"), self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = SmcError;
+
+            fn from_str(s: &str) -> Result<Self, SmcError> {
+                crate::hex::parse_hex(s, $width).map(|v| $name(v as $int))
+            }
+        }
+
+        impl fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::LowerHex::fmt(&self.0, f)
+            }
+        }
+
+        impl fmt::UpperHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::UpperHex::fmt(&self.0, f)
+            }
+        }
+
+        impl From<$int> for $name {
+            #[inline]
+            fn from(h: $int) -> Self {
+                $name(h)
+            }
+        }
+
+        impl From<$name> for $int {
+            #[inline]
+            fn from(h: $name) -> Self {
+                h.0
+            }
+        }
+
+        impl PartialEq<$int> for $name {
+            #[inline]
+            fn eq(&self, other: &$int) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+impl_hash_type!(Hash64, u64, 16);
+impl_hash_type!(Hash128, u128, 32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_hash64_format() {
+        assert_eq!(format!("{}", Hash64(0x1)), "0000000000000001");
+        assert_eq!(format!("{}", Hash64(u64::MAX)), "ffffffffffffffff");
+        assert_eq!(format!("{:x}", Hash64(0xabc)), "abc");
+        assert_eq!(format!("{:#018X}", Hash64(0xabc)), "0x0000000000000ABC");
+
+        let h = smchash_typed(b"hello");
+        assert_eq!(h, smchash(b"hello"));
+        assert_eq!(u64::from(h), smchash(b"hello"));
+        assert_eq!(Hash64::from(7), Hash64(7));
+    }
+
+    #[test]
+    fn test_hash128_format() {
+        let h = Hash128(0x0123456789abcdef_fedcba9876543210);
+        assert_eq!(format!("{}", h), "0123456789abcdeffedcba9876543210");
+        assert_eq!(format!("{}", Hash128(1)), format!("{:032x}", 1));
+        assert_eq!(format!("{:X}", h), "123456789ABCDEFFEDCBA9876543210");
+        assert_eq!(h, 0x0123456789abcdef_fedcba9876543210u128);
+        assert_eq!(format!("{}", h).as_bytes(), &crate::hash128_to_hex(h.0));
+        assert_eq!(format!("{}", h).parse::<Hash128>(), Ok(h));
+        assert_eq!("0123456789ABCDEFFEDCBA9876543210".parse::<Hash128>(), Ok(h));
+    }
+}
@@ -0,0 +1,342 @@
+//! Fixed test vectors guarding cross-platform determinism.
+//!
+//! Every value here is hardcoded. The unseeded 64-bit hashes and the PRNG
+//! values match the C reference implementation on x86_64. The same tests run
+//! unchanged on big-endian targets (`cargo test --target s390x-unknown-linux-gnu`
+//! or `powerpc64-unknown-linux-gnu` under qemu), so any endian or width
+//! dependence shows up as a failure rather than as silently different stored
+//! hashes.
+//!
+//! The same holds for `cargo test --features small`: the size-optimized
+//! build must reproduce every value here, including `BY_LENGTH` for each
+//! length up to 520.
+
+use crate::*;
+use alloc::vec::Vec;
+
+/// Input for the vectors: byte `i` is `i * 7 + 1`
+fn input() -> Vec<u8> {
+    (0..1000u32).map(|i| (i * 7 + 1) as u8).collect()
+}
+
+/// `(len, smchash, smchash_seeded(.., 0x0123456789abcdef))`
+///
+/// Above 16 bytes the seeded values differ from the C header's
+/// `smchash_seeded`, which derives its long-input seed from `SMC_SECRET[0]`
+/// where this crate (and C's unseeded `smchash`) uses `SMC_SECRET[2]`.
+const HASHES: [(usize, u64, u64); 14] = [
+    (0, 0x76eee9b64c443120, 0xf97250bb1df342d7),
+    (1, 0xdb4dfa492a02b5d7, 0x82997260623ea763),
+    (3, 0x448a70db698c5373, 0xf57769a1c761da33),
+    (4, 0xebbfc2c2794cd50e, 0x5aaf13f747d3405e),
+    (7, 0x8c58ca673f0ad067, 0x8699f451be3aeb26),
+    (8, 0x8fca63881dac13f4, 0x60412e763c007776),
+    (16, 0xf7074fdc2ee6a28f, 0xafce26fc6724d6fa),
+    (17, 0x838302c5ca9f6aa1, 0x60e285f13b078cff),
+    (33, 0xf9fdd20c600c71f5, 0xc445319f3c0e7a5d),
+    (65, 0x22d14f89e30ffde4, 0x24cb4f9a02f1719a),
+    (128, 0x54d560223587528f, 0xddd76227cb18a9c1),
+    (129, 0x8302bcdd482c4790, 0xf3c96304d99854f7),
+    (256, 0x2816c7acdf3a0499, 0x07d9155e1bfbc990),
+    (1000, 0x98fab049124c5a7f, 0x74ad0ac05efe422f),
+];
+
+const HASHES128: [(usize, u128); 4] = [
+    (0, 0xfaf88c3b2cd2548476eee9b64c443120),
+    (17, 0xc1047ebf39ede886838302c5ca9f6aa1),
+    (129, 0x7f427ee198b675a68302bcdd482c4790),
+    (1000, 0x164048fec15e238198fab049124c5a7f),
+];
+
+/// `smchash(&input()[..len])` for every `len` in `0..=520`, from the C reference.
+///
+/// Covers each path in the length dispatch (empty, 1-3, 4-7, 8-16, the 17-128
+/// tail steps, and one to three bulk blocks followed by every tail length)
+/// so any off-by-one in the block logic changes at least one entry.
+#[rustfmt::skip]
+const BY_LENGTH: [u64; 521] = [
+    0x76eee9b64c443120, 0xdb4dfa492a02b5d7, 0xbf49732cdff7c25a, 0x448a70db698c5373,
+    0xebbfc2c2794cd50e, 0xc65a1d3b913da3ee, 0xc6158e2e21f3dc02, 0x8c58ca673f0ad067,
+    0x8fca63881dac13f4, 0xe64eee8eeb0de48c, 0x0ba2e68481e3861f, 0xff5b76c7390eb909,
+    0xf63d3a3605cf090b, 0x734d9567332df3c7, 0x72cd44b15aeeea37, 0xfcd46fe8078daf12,
+    0xf7074fdc2ee6a28f, 0x838302c5ca9f6aa1, 0xbeb7af597bd54d68, 0x22ddad1fc51850a2,
+    0xabe199717c24a493, 0xea488aa243ad4bdf, 0xeb02e8224d5f3885, 0x86a56682460923d9,
+    0xd2b7c44a8acf5c35, 0x3a8be5a80769766b, 0x678ec41fb9b2466d, 0x912321817b0ebdc4,
+    0x610cadeae04893bd, 0x19a97378c1c3349b, 0x8e17ae5212406d2c, 0xa026fa7ed349a7ba,
+    0x258f3a5093ac95a5, 0xf9fdd20c600c71f5, 0xe8fbd7f756119b59, 0x74110c0088c027c1,
+    0x4bb1e6f09befa7f8, 0x4534bb09fba91523, 0xbedb6c6e09eedd6e, 0x5702dde1ad832c38,
+    0x30e944dbbc258495, 0x219110121119e65a, 0x7ade7825b1aeaa9c, 0x9197cbfd0234ac11,
+    0x3996ac64775a1c31, 0x629ea7cd13cce0aa, 0xf0f217d73fea85be, 0x4d3c6fc6384c8e54,
+    0xa2e943d9a1bfbf44, 0x223f00a18f3f2f41, 0x26f0e132248234fa, 0x221c18463fc94985,
+    0x6cb9e7194e539ca0, 0xca1ed5a8b97d5976, 0xf830a2369212a9ec, 0xdc2ed32f2ed8c7b8,
+    0xe3a2464da6db382c, 0xc7aff8d4d145acfd, 0x58d25cd8a122579e, 0xa02eecdac42c822b,
+    0x2d1c9bdfc90255a0, 0x3963ddb1da4a7a1c, 0x8fb90e86ffa34ed2, 0x69acb050c40bc80e,
+    0xe919d88610d5f11c, 0x22d14f89e30ffde4, 0xbae69273304e6b1f, 0xe2455795ca5ec084,
+    0x29033bbc480dd68d, 0x3ee748cf64cc0d1e, 0x36607928eda07833, 0x1b88082ee8bdf505,
+    0xa395cbccbc199e50, 0xcc7f54ef4b6ad8ae, 0x96be6f49a7ef3db4, 0xaf1b767133ccddec,
+    0x4d9f10511f71d975, 0xaa91d627c4e82c3f, 0x6ef879b8b8435c55, 0xdc810b142555871f,
+    0x45592f2e74002654, 0x0868f7b2edae59f6, 0x1aea3a3486d99f33, 0xcc526f5acabaca8c,
+    0x9fc25462bd718640, 0xcbfbf3cb3ffd021f, 0x627016e46d997920, 0xdb754ac22d184e00,
+    0x8daad8a9ef0034b2, 0x0b6ec3b6cb18ecc7, 0x82bb5ae9d1370cf2, 0x2bed75b472c3a736,
+    0x9c05044224018305, 0xe38f7bfd9e066b1d, 0x92920b1fa82f1fe2, 0x962a60b7edf3497e,
+    0x55d930db6e85ae2b, 0xd47a879dd9b5784b, 0xb9d32d2b3799c4bc, 0x99c3a47ecc2cba8a,
+    0x6426d84fdd40c895, 0x4ed8970aea8fb37f, 0x50986bb4923561a6, 0xc0e7ce084a966f91,
+    0x7b04443f6e3d600f, 0xfeef7583c12a676a, 0x7070d623a938d21c, 0xab70788c41712967,
+    0xc2b8b4f4f8a5eb7e, 0xdc7286c709f38a29, 0x5938b0b6ab89eb90, 0x3cbebf6bd2442d4f,
+    0x4dd9a71a33a03743, 0x8748d69febbbdf79, 0xe1d8c22eb5495186, 0x0356bd07344596df,
+    0x2dd435ead77a0d97, 0xe8e2425759be4a01, 0x38ce809726b0d0f1, 0x2ce6a5bce5229b35,
+    0x5d8233331cca450a, 0xec38adddcd1e2800, 0x1a2674c64ecdba66, 0x509c2dbf9123c626,
+    0x69a643fe389b2cab, 0x513d5866a51717c9, 0x9e455e7ccc33dc7b, 0x078ad6e7fe9c3f43,
+    0x54d560223587528f, 0x8302bcdd482c4790, 0xa0a55b30276a9c8e, 0x40d4c189252d7d1a,
+    0x127bc034c2a33e7e, 0x0b6498ade2940fac, 0x925f1c0ec63b85f7, 0x74e5b933f5bd22e5,
+    0x3e192de5252dd708, 0xe07c0ef02140ae01, 0x9f832682a4c182d1, 0x743cdafc8a91814e,
+    0xf4caa6208e238d2f, 0x32ec3d3f2ec11d6d, 0xcc5c70e922374611, 0x108a160eba007ea3,
+    0x0b18973c6acc5794, 0xaf386a223d98fd36, 0x9f0dd323810de8db, 0x33ff9fd33e2b221b,
+    0x162c193e66a36b1b, 0x11d171480d52153b, 0x643f6e9e97cb2ff8, 0x150885dfe61285d1,
+    0x3295ee94cd05c6f1, 0x075759967ec6fef0, 0x9e8054e6a8ee836a, 0xab96977fbb186816,
+    0x3f14cf9d478b00ef, 0x540871d6b6ae5099, 0x59a6ae743398abb9, 0xbd66a26491fdcb55,
+    0x65930eb0d21e46a1, 0x530b7cb63932e59e, 0xc55467a21437fb1b, 0x4c57696ef7978b83,
+    0x7700f37e52859c68, 0x1476ef0cf3f5f4ed, 0x94a9c03d363a54de, 0xaa2197d0d23cde94,
+    0xf853f7be594735e2, 0x7e6099e2b22897ea, 0x2f75895183f76f1d, 0x0c2fe048ab5a68be,
+    0x7696bf5266e5cb77, 0x97215fc7e0cce524, 0xf54e6f51f56fbc70, 0x0a379cab1654541a,
+    0xb5d6e5249da416ab, 0x6e186f20d85a61b9, 0xcf1cd3220c26c028, 0xbe3988bd14e55d9e,
+    0xeb203d2690979008, 0x25c96b42eeef657d, 0x26b7c2dce7b2d065, 0x2740513560240094,
+    0xc01d12d0a5e44f2d, 0x9b49dc9185a8c54b, 0xb32300647e9e7ec1, 0xbd6ce5e824e065fa,
+    0xbe5677d91f40d545, 0x1b95958ac305638a, 0x245001dd64421a97, 0x35aec563eff917e1,
+    0xf1b03287cbbd0605, 0x89847baf774821c0, 0xc7d09a11e5121aee, 0x5dc4463847af247f,
+    0x85449989f60755e3, 0xd5cbca76a4c61527, 0x6dbe9d15c55aff7a, 0x67e188fb91983e17,
+    0x1f9fc3243ff74ffb, 0xe8c36b9ddb279e44, 0xb8b932083a7e9766, 0x9f2d1a8f4ef14f5f,
+    0xc1e36fe405d82aba, 0xcd7e78e751c28116, 0x87d28ccf4240032c, 0x952f55feaa6b4868,
+    0x93b84e00267607e2, 0x2b996635434e3b76, 0x69705f5c31615905, 0x71cdeb6443cc32ea,
+    0xbb56a1bd4f832a8d, 0xf27b440bde18deb0, 0x9e3eaaf274d74016, 0x35b8144e572a428f,
+    0x1f6dd205205648a8, 0x6915cf10a7eb4ef3, 0x56bc1a9a76d77d36, 0xddb53937d0074540,
+    0xa4c26de80e49b864, 0x13551c666fef32f6, 0xc96fd416c2493352, 0x9c2f2ba0b51a39ac,
+    0xe3f3cfd9d37dd0a9, 0xe4d2bbe467e153b3, 0xf38baf96e6a17e47, 0x4b0e4aa6e5b3de2e,
+    0x3019481b617a3125, 0x817c5b1534d8f766, 0x60d460b244bec3d1, 0x8bddfae6b0ee7c1b,
+    0xe10e05d1b395e777, 0x16baff9add33e318, 0xef14131425a845cd, 0x2ac22e3cbd898605,
+    0x834a535b058a199c, 0xb5db985dfb1c296f, 0x78ef46969d9f4f7e, 0xf59f3b9fbd84c7b8,
+    0x34f2ff5c17017bde, 0x3a2062cd64016711, 0x3ce7a7b57692c378, 0x2a0616738f1864f8,
+    0xec5430ae1b53fb2b, 0xe03840d8a13fc615, 0xb46dca08ae2ce501, 0x42a3a9000fecebe6,
+    0xee9fe8d1181cfae3, 0x9ff34de25a4e854e, 0xf9744082c4258362, 0x4b9e7acde354fa40,
+    0x440a07c1e6339c4a, 0xe8ed2748e7777ee3, 0xdf27e901999c45cb, 0x6e64f4f856c4e2ee,
+    0x2816c7acdf3a0499, 0xbf72e7c1ca11d7ea, 0xb543fe630f4021a6, 0x6d1f72577a07d413,
+    0x5019ffa6e01dae8b, 0xda277dce8f0b356a, 0x6db3775be57a0aa2, 0x25d21fad92f87519,
+    0x7c357b4c8dd9f4ab, 0x91da0f947b0d981d, 0x1aa9a5cfe829f925, 0x26a448abc1dc6229,
+    0x58f833a493609947, 0x0c51cbeab7bba33f, 0xa7729271a0e2a47f, 0xa544dee32dce0ad4,
+    0x3165cf677e813e55, 0x680c81a1483c41ac, 0x2dc57c8c14242889, 0xba387516a575d871,
+    0xde570f9413092982, 0x48aeb3c793802db4, 0xcdf3c6fbfff005e9, 0x80be507cb288bcf5,
+    0x9580ea2311f7d20f, 0x81eda1eb856d7574, 0x61bf55330fe9d1eb, 0xab15088d16ee113a,
+    0x5e54d46513b81d4d, 0xf32218dc3bc6b522, 0x1293795b8434d20c, 0x6f80c295eec58031,
+    0xc5ae80d4640a916d, 0x265e488ce9f72c65, 0x45ad5899fe6428e0, 0xddb86a3417c9f912,
+    0x74bd4f8116128ef6, 0x81d48769c40f888f, 0x1e095d95c8f98006, 0x8cb9bd33b2143568,
+    0xd26acd5c5740320a, 0xf8eda1022b767154, 0xa647c7a8f91ac1d3, 0xf872341a60ca954c,
+    0x72a7d4a1b104d05a, 0x0e2f608d5a11c182, 0x4012bd690774ef72, 0x160c9f3b5c4b5271,
+    0xe3e2f9423c085737, 0x39a068a8b922a890, 0xd8e40118c57f7576, 0x170428260459397d,
+    0x4dcbbeb88c5455a2, 0x0e97b70d2469a791, 0xca774ba0d61f3434, 0x4a3d6748e0bd7cc7,
+    0x0021ebf0c86dd31b, 0x7f859a0d8d97e90f, 0x38c89404c8b9ace4, 0x359364545b1ea7b8,
+    0x2796fe6d06c7a8a1, 0x5dfa96467fbef478, 0x3870179b4fbb2354, 0x0da28d75d046ff7a,
+    0x9e9921dbbbe1c835, 0xac85885d7f0b611b, 0x00ad84f40180fc87, 0x159d13d5a64102cc,
+    0xfa1bb3ac58a2c47b, 0xe729881a8c049fb1, 0x8faed7c3e34a3add, 0xd6c3d7d7562b640b,
+    0x7d690603967c7a9f, 0xc554dc4b4475ece9, 0xa2823e492af02bf1, 0x530cc095bdeb9764,
+    0x6b3a83143d497b83, 0x4ce0291ed11fe197, 0x21cc23816370e802, 0xf1f16d6f1fc2ced6,
+    0x9e1a0b9896005522, 0x2a7b13a25216c63a, 0x2629251ad122f45e, 0x276bbc4ad9c534a9,
+    0x2368f34ab6f836f4, 0x1477c60102d691ec, 0x5a632244ad899afc, 0xe89a9c0047a94656,
+    0x88cbb16a9e94dc34, 0x51b95007cb92cfcb, 0x6750449d85939c4a, 0x94521b098e0a2179,
+    0xdf9c4effa7ddb707, 0xf3c1a9f461e32375, 0x4ea53ffad9ee016b, 0xe9464a9cba4e1621,
+    0x5e948e54d90f4747, 0xb0a30fe303626fa8, 0xfaeb1a43a3f82975, 0x4cd123f80f0e8743,
+    0x5b452c26e3975334, 0x69426abc927eebfa, 0x893bfdc07c98a9c4, 0x4bbd68e3a2ccdf6b,
+    0xf6f196c8354ab4a3, 0x82ceb91c31406277, 0x86d1dfd9eaa2ce6b, 0x2e0469c36062819e,
+    0x0f81d88523ced15a, 0x77c72a6ea1a2bf41, 0x34208352dd63277f, 0x89e1356808ab7417,
+    0xf11b0ffd8314bd82, 0x9b35d98fc52ef542, 0xc581252547c36ca3, 0x484be95e017c9287,
+    0xf85f5230e521ad1e, 0xf2af8df6c91c5e1f, 0x6db4149e3240dca9, 0x43ef18baec054767,
+    0x2d78728a811a1219, 0x6d5717738cf87d22, 0x01508e10ab609c34, 0xeb030cf3babfc00d,
+    0x4b27118d1a35e6b9, 0xf55a3aa144e3ac37, 0xfb3cda57b08ce65f, 0xadb624f06b3a1b39,
+    0x0f31812a918f6a1d, 0x80fac76b61690640, 0x973250f708a75869, 0x63959829e2c86243,
+    0x66dae136a635853e, 0x37c489c7e51cf00b, 0xd4111f7d6c88176a, 0xd005081c3285b554,
+    0xa552b6c20dabeb3f, 0x7a85141ed09cc256, 0xe75bdc3b7bf546fa, 0x0a0230a37267a4b3,
+    0x28d111587a3d39d8, 0xc4cc468934d4bfd7, 0xcc3f85559cf030cb, 0x03daf3a9ce37cef0,
+    0xa1733d215d9869fa, 0x3dd6b3b03975a524, 0x2d78772e062b88d1, 0x7162db135e601c1c,
+    0xef57500c3c3774b2, 0x480a1edfa1d8dab0, 0xe39c7d6d8e87b9a6, 0x9e33a69627fc9af3,
+    0x4f09c525e8c640b5, 0xd9a9aecfbde9aac7, 0x89989157e946fa1d, 0xd1acc3761b3268fe,
+    0xc56f9e15743e2c0e, 0x0519265c2ae14a0b, 0xf7c963888ff4fe24, 0x60053052ca46e9c0,
+    0xea17f60f0cfc01eb, 0x40c767de23c24ef7, 0xdb71735c5dfe1edb, 0x7e50f62d648eb9fe,
+    0x92cc701af4832788, 0x4bcfdf610d16f83f, 0xcf5600fdeef3ab7d, 0x07a6c764274d6cb1,
+    0x0b819953518d1c59, 0x7001ad34351a6e65, 0xf880a4479f944c9a, 0xb4850a42eb8bbf81,
+    0xda5f788f6ae762c9, 0x6cf43861e2033b21, 0x9eccd65708f5519f, 0x423d1b43999f3a31,
+    0x521fc2478dbfa755, 0x1ad9190ec18e0a17, 0x32e11198b3c639aa, 0x093ceffe3e092d98,
+    0x8df1832050092f44, 0x3072d4b778dabcb3, 0x77cd1cf24a148f28, 0xf299593e303a4012,
+    0x3d97250da234037a, 0x0b5b2f4ccef4e658, 0x3149bde1f1dbd8e7, 0x5967b60105fd755f,
+    0xa87faf5582e85cc5, 0x68384057531c151c, 0xbb5c5d37a22f0737, 0x494053ee7b482465,
+    0xc9cd49f125947ed6, 0x72eb621ca512f2a3, 0xa223094b6c294a05, 0x89b59fb0c2abf164,
+    0x9400f8927c5ee0ca, 0x0cd49d3565a57319, 0x81195d9c622b8f40, 0x5b0f15e5eb7164c6,
+    0x74b1e453207ef9d9, 0x6487ea6e6000cead, 0x1aa60aa219fbc1b0, 0xb5d0ad892d887ce9,
+    0xeddf63a330e8c7c0, 0xdcefb3c9b5a125c0, 0x73ddebef28dc82ec, 0xb1a6516d8c3b722b,
+    0xf7eaa70d58cab2d5, 0xad634f123175f9e3, 0x831172e98ca46766, 0x804036005dff8a0d,
+    0xe3902e017f8791c7, 0x2061b25f12201a25, 0x748ebce5624653f3, 0x3325d3cf0d6bfb6e,
+    0xba96b675eaf221cd, 0xb6748b1dcfcaf31a, 0x4c3e7812ee5073de, 0x0766fbf966927edd,
+    0xbc02b9156be52e18, 0xfc89f1309f76600b, 0x5319433e0ecc3213, 0x11633dd19bf7e9c8,
+    0xef7e55a6fe13be07, 0x0499cb79d08b1115, 0x9e69d811b0c8f699, 0x21e4ab57abbf841b,
+    0xc64b6f4e3c77338f, 0x9187540a0d4f45d7, 0x0add54da75607747, 0x5293621e7d1fe1d5,
+    0x6fec89a14fa89333, 0xc3f6b027eb34c7ca, 0xd78dc8a61207903b, 0x9a43fb1aa9a7e5ca,
+    0xecc82a5e5b7fe1dc, 0xed8e5ab50850a5cd, 0xa3276c7dd6ff1e86, 0x34c5e72c49a70053,
+    0x4249742f0162ca65, 0xa0ecb37faaceb806, 0xd2afc752b8172fd0, 0xcd496adbef63f56e,
+    0x32bca670cf06b0c5, 0x86d36cd1642a24de, 0x26ca93151a1e700d, 0xa1d4017770e84606,
+    0x5d4dbd6ed5f743c2, 0x489a56b71b764632, 0x67580ba89155a659, 0x5a49d9f57899e0dc,
+    0xc1b03bf425666bbc, 0x5998ec172138122a, 0x192ede3f5a38a7eb, 0x1ad40b81c048eaca,
+    0xcdbff4669351d13f, 0xe03389ffe7ee8703, 0x5ba2a3e38e3c19db, 0x58911d318e8fcfa5,
+    0x2aba20bde6c2ae51, 0xd35bf0b8d88ac554, 0x6065aeca90644113, 0x3f53b903b9b22e9c,
+    0x01eccca49beea098,
+];
+
+#[test]
+fn test_vectors_every_length() {
+    let d = input();
+    for (len, &expected) in BY_LENGTH.iter().enumerate() {
+        let data = &d[..len];
+        assert_eq!(smchash(data), expected, "len {}", len);
+        assert_eq!(smchash_const(data), expected, "const, len {}", len);
+        assert_eq!(
+            smchash_multi(&[&data[..len / 3], &data[len / 3..]], SMC_SECRET[0]),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_vectors_hash() {
+    let d = input();
+    for &(len, hash, seeded) in &HASHES {
+        let data = &d[..len];
+        assert_eq!(smchash(data), hash, "len {}", len);
+        assert_eq!(smchash_const(data), hash, "len {}", len);
+        assert_eq!(
+            smchash_seeded(data, 0x0123456789abcdef),
+            seeded,
+            "len {}",
+            len
+        );
+
+        let mut hasher = SmcHasher::new();
+        for c in data.chunks(13) {
+            hasher.update(c);
+        }
+        assert_eq!(hasher.finish(), hash, "len {}", len);
+    }
+}
+
+/// `smchash128_seeded(.., 0x0123456789abcdef)`; low halves match `HASHES`
+const HASHES128_SEEDED: [(usize, u128); 3] = [
+    (0, 0x41362442ef01e433f97250bb1df342d7),
+    (17, 0x501adb8d74aff36560e285f13b078cff),
+    (129, 0xefc27db27901ea32f3c96304d99854f7),
+];
+
+#[test]
+fn test_vectors_hash128() {
+    let d = input();
+    for &(len, hash) in &HASHES128 {
+        assert_eq!(smchash128(&d[..len]), hash, "len {}", len);
+    }
+    for &(len, hash) in &HASHES128_SEEDED {
+        assert_eq!(
+            smchash128_seeded(&d[..len], 0x0123456789abcdef),
+            hash,
+            "len {}",
+            len
+        );
+    }
+}
+
+#[test]
+fn test_vectors_other() {
+    let d = input();
+    let words: Vec<u64> = (0..40u64)
+        .map(|i| i.wrapping_mul(0x9e3779b97f4a7c15))
+        .collect();
+    assert_eq!(smchash_u64_slice(&words, 0), 0xd99896dfaa3017d5);
+    assert_eq!(smchash_tree(&d, 100, 0), 0xa38a8969ca96fd00);
+}
+
+#[test]
+fn test_vectors_rand() {
+    let mut seed = 7u64;
+    assert_eq!(smc_rand(&mut seed), 0x432cb73811300810);
+    assert_eq!(smc_rand(&mut seed), 0x34be33d7c51bc139);
+    assert_eq!(smc_rand(&mut seed), 0xd403254d471dca15);
+
+    // Byte output is little-endian on every target
+    let mut seed = 7u64;
+    let mut bytes = [0u8; 11];
+    fill_bytes(&mut seed, &mut bytes);
+    assert_eq!(bytes, [16, 8, 48, 17, 56, 183, 44, 67, 57, 193, 27]);
+}
+
+/// The canonical short inputs, in `SHORT` order: the empty input, then for
+/// each length `1..=8` all zeros, all `0xff`, ascending bytes `1, 2, ..`, and
+/// one input per position with only that byte set to `0x80`. Single-byte
+/// inputs therefore pin where each byte lands in the 1-3, 4-7 and 8-byte
+/// packings.
+fn short_inputs() -> Vec<Vec<u8>> {
+    let mut inputs = alloc::vec![Vec::new()];
+    for len in 1..=8usize {
+        inputs.push(alloc::vec![0u8; len]);
+        inputs.push(alloc::vec![0xffu8; len]);
+        inputs.push((1..=len as u8).collect());
+        for pos in 0..len {
+            let mut key = alloc::vec![0u8; len];
+            key[pos] = 0x80;
+            inputs.push(key);
+        }
+    }
+    inputs
+}
+
+/// `smchash` of each of [`short_inputs`], in order.
+///
+/// Regenerate only for an intentional output change (which also bumps
+/// `SMCHASH_VERSION`) with `cargo test --lib print_short_table -- --ignored --nocapture`.
+#[rustfmt::skip]
+const SHORT: [u64; 61] = [
+    0x76eee9b64c443120, 0x6b3001cf5c079483, 0x9b8c99ce548d63fc, 0xdb4dfa492a02b5d7,
+    0xbe2249e9a0f44ce2, 0x2e5298246fcb79e6, 0xf1ea505d16ae61d4, 0xe23b4edf399030e8,
+    0xbf7fdc57b6badf84, 0x6ce16c06911ba504, 0x42a4b07d7f8add41, 0x2a4ba6d978a5dfdb,
+    0xc8d6fabc7580cac4, 0xa24c09ba1b49a978, 0xdb1c2990d2eaba5a, 0xa55efc80fe085479,
+    0x05c54b5b8f4fa6a4, 0x9e16d95b504998e4, 0xba37caea44d60e89, 0x2ddd0bde47ea9c61,
+    0x138bdbaaaeceafc6, 0x3c65c03cc8a11b84, 0x745b2dfbb764ae48, 0x3a0f60ec9f091a07,
+    0xd12553ac36a673fc, 0x4fd2e970076d0df3, 0x03db82dcf037430a, 0xd5b034527e4db81b,
+    0x436aad7474ac0694, 0x81428a0c7f0b8000, 0x126c68331dea620b, 0xfda9fbc9aed2ef69,
+    0x1cf7c245668dce95, 0x460c4a10d934c89f, 0x1be689eac94a3734, 0xacae61ea00edc3fc,
+    0x078e784f97afbde8, 0xb575300ca27b0fe1, 0x083265710e149929, 0xd1caa4be09215759,
+    0x11f31302be8c42c5, 0x5f065cd67deba1ef, 0x7e9e8dee7315f614, 0x68a750f8ca5ebedb,
+    0xa75b1944469cba5a, 0xd2d6022855a70556, 0x4457a46ad3ddf6a7, 0xdefb5dccae2a5cfb,
+    0x09bb60f12af814dc, 0x9090e124e5780b57, 0xd51daa7fce51542b, 0x84c1939213fed459,
+    0xb7657c53c96722f4, 0x9a47a6fb3a11c766, 0x7e69befa501bfa42, 0xebe450fd9bb4a56d,
+    0xa2084d26d389b839, 0x713ddceac1e48fcd, 0xa5732697f4f84ed1, 0x1627c1a9fc20ca1c,
+    0x51cd5d555fe979e3,
+];
+
+#[test]
+fn test_vectors_short() {
+    let inputs = short_inputs();
+    assert_eq!(inputs.len(), SHORT.len());
+    for (key, &expected) in inputs.iter().zip(&SHORT) {
+        assert_eq!(smchash(key), expected, "input {:02x?}", key);
+        assert_eq!(smchash_const(key), expected, "const, input {:02x?}", key);
+    }
+}
+
+#[test]
+#[ignore = "prints the SHORT table for regeneration"]
+fn print_short_table() {
+    extern crate std;
+    let inputs = short_inputs();
+    std::println!("const SHORT: [u64; {}] = [", inputs.len());
+    for row in inputs.chunks(4) {
+        let cells: Vec<_> = row
+            .iter()
+            .map(|k| alloc::format!("{:#018x},", smchash(k)))
+            .collect();
+        std::println!("    {}", cells.join(" "));
+    }
+    std::println!("];");
+}
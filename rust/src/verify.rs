@@ -0,0 +1,157 @@
+//! Known-answer self test.
+
+use core::hint::black_box;
+
+use crate::{smchash, smchash_secret, smchash_seeded, SMC_SECRET};
+
+/// Version of the hash output format.
+///
+/// Bumped whenever any function's output changes for any input: the hash
+/// functions, the PRNG, or the derived constructions built on them. Outputs
+/// never change without a bump, and a bump only happens in a semver-breaking
+/// release. Store it next to persisted hashes and check it with
+/// [`verify_version`] at startup.
+pub const SMCHASH_VERSION: u32 = 1;
+
+/// Custom secret for the known-answer test (`smc_make_secret(0)`)
+const KAT_SECRET: [u64; 9] = [
+    0xf03aa5f0392b74c3,
+    0xa6ca3378b4cc6c35,
+    0xe1274b4b960f1ec5,
+    0xe46cac0f4d8d5933,
+    0xd4b8478d3cd42bc9,
+    0xa3d24bc96335711b,
+    0xb85a9a1e5578178d,
+    0x636596992ee8b499,
+    0x635cd16a99d18d4b,
+];
+
+// Known answers for SMCHASH_VERSION 1; they change together with the version.
+
+/// `smchash(b"")`
+const KAT_EMPTY: u64 = 0x76eee9b64c443120;
+/// `smchash(b"Hello, World!")`
+const KAT_HELLO: u64 = 0x25bb0982c5c0de6e;
+/// `smchash(&[0, 1, ..., 128])`
+const KAT_129: u64 = 0x9374cbe3490b53c0;
+/// `smchash_seeded(b"Hello, World!", 12345)`
+const KAT_SEEDED: u64 = 0xd26cb494f911af5b;
+/// `smchash_secret(b"Hello, World!", 0, &KAT_SECRET)`
+const KAT_SECRET_HELLO: u64 = 0x7a3c5071f318a268;
+/// `smchash_secret(&[0, 1, ..., 128], 0, &KAT_SECRET)`
+const KAT_SECRET_129: u64 = 0x3c3b04959235470b;
+
+/// Run the known-answer tests and report whether all of them pass.
+///
+/// Hashes a fixed set of inputs (empty, short, a 129-byte buffer that takes
+/// the bulk path, a seeded case and two custom-secret cases) and compares
+/// against hardcoded values that match the C reference implementation, then
+/// checks [`smhasher_verification`], which covers every length below 256 and
+/// is also computed the C way (see its docs for the seeded divergence). Use
+/// it as a startup integrity check to catch miscompilation or accidental
+/// algorithm changes.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(smchash::verify());
+/// ```
+#[must_use]
+pub fn verify() -> bool {
+    let mut buf = [0u8; 129];
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    // Keep the compiler from evaluating the checks at build time
+    let buf = black_box(&buf[..]);
+    let hello = black_box(&b"Hello, World!"[..]);
+    let secret = black_box(&KAT_SECRET);
+
+    smchash(black_box(b"")) == KAT_EMPTY
+        && smchash(hello) == KAT_HELLO
+        && smchash(buf) == KAT_129
+        && smchash_seeded(hello, black_box(12345)) == KAT_SEEDED
+        && smchash_secret(hello, 0, secret) == KAT_SECRET_HELLO
+        && smchash_secret(buf, 0, secret) == KAT_SECRET_129
+        && smhasher_verification() == SMHASHER_VERIFICATION
+}
+
+/// Expected [`smhasher_verification`] result for [`SMCHASH_VERSION`] 1
+const SMHASHER_VERIFICATION: u32 = 0xa12046c1;
+
+/// Compute the SMHasher verification value of the C header's
+/// `smchash_seeded`.
+///
+/// Follows SMHasher's `VerificationTest`: for each `i` in `0..256`, hash the
+/// key `[0, 1, ..., i - 1]` with seed `256 - i` (SMHasher's convention, not
+/// the key index) and append the 64-bit result in little-endian order, then
+/// hash the 2048-byte concatenation with seed 0 and return the low 32 bits.
+///
+/// The hashes are computed the way the C header's `smchash_seeded` does,
+/// which is what SMHasher builds register: above 16 bytes it derives the
+/// seed from `SMC_SECRET[0]`, as [`smchash_secret`] with the default secret
+/// does, where this crate's [`smchash_seeded`] uses `SMC_SECRET[2]`. The
+/// result, `0xa12046c1` for format version 1, therefore matches the value
+/// SMHasher prints for smcHash; a loop over [`smchash_seeded`] would give a
+/// different, Rust-only value.
+///
+/// Runs on the stack and needs no allocation.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(smchash::smhasher_verification(), 0xa12046c1);
+/// ```
+#[must_use]
+pub fn smhasher_verification() -> u32 {
+    let mut key = [0u8; 256];
+    let mut hashes = [0u8; 256 * 8];
+    for (i, out) in hashes.chunks_exact_mut(8).enumerate() {
+        key[i] = i as u8;
+        let h = smchash_secret(black_box(&key[..i]), 256 - i as u64, &SMC_SECRET);
+        out.copy_from_slice(&h.to_le_bytes());
+    }
+    smchash_secret(&hashes, 0, &SMC_SECRET) as u32
+}
+
+/// Check that stored hashes made with format `expected` are still valid.
+///
+/// Returns `true` only if `expected` equals [`SMCHASH_VERSION`] and the
+/// known-answer tests of [`verify`] pass, i.e. this build computes exactly
+/// the values that version promises.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{verify_version, SMCHASH_VERSION};
+///
+/// // Written alongside the hashes when they were persisted
+/// let on_disk = SMCHASH_VERSION;
+/// assert!(verify_version(on_disk), "hash format changed; rebuild the index");
+/// ```
+#[must_use]
+pub fn verify_version(expected: u32) -> bool {
+    expected == SMCHASH_VERSION && verify()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        assert!(verify());
+    }
+
+    #[test]
+    fn test_smhasher_verification() {
+        assert_eq!(smhasher_verification(), SMHASHER_VERIFICATION);
+    }
+
+    #[test]
+    fn test_verify_version() {
+        assert!(verify_version(SMCHASH_VERSION));
+        assert!(!verify_version(SMCHASH_VERSION + 1));
+        assert!(!verify_version(0));
+    }
+}
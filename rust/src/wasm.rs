@@ -0,0 +1,41 @@
+//! JavaScript bindings via `wasm-bindgen` (requires the `wasm` feature).
+//!
+//! Build with `wasm-pack build --features wasm` (add
+//! `--no-default-features` to leave out the `std`-only helpers). Hashes are
+//! returned to JavaScript as `BigInt`, and seeds are passed as `BigInt`.
+//!
+//! ```js
+//! import { smchash_js } from "smchash";
+//! const h = smchash_js(new TextEncoder().encode("Hello, World!"));
+//! // h === 0x25bb0982c5c0de6en
+//! ```
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{smchash, smchash_seeded};
+
+/// [`smchash`](fn@smchash) for JavaScript: takes a `Uint8Array`, returns a `BigInt`.
+#[wasm_bindgen]
+pub fn smchash_js(data: &[u8]) -> u64 {
+    smchash(data)
+}
+
+/// [`smchash_seeded`] for JavaScript: the seed is a `BigInt`.
+#[wasm_bindgen]
+pub fn smchash_seeded_js(data: &[u8], seed: u64) -> u64 {
+    smchash_seeded(data, seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_js_exports() {
+        assert_eq!(smchash_js(b"Hello, World!"), 0x25bb0982c5c0de6e);
+        assert_eq!(
+            smchash_seeded_js(b"Hello, World!", 12345),
+            0xd26cb494f911af5b
+        );
+    }
+}
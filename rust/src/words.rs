@@ -0,0 +1,170 @@
+//! Hashing native integers without byte conversion.
+
+use crate::bulk::fold;
+use crate::primitives::mum_pair;
+use crate::{mix, SMC_SECRET};
+
+/// Hash a slice of `u64` values.
+///
+/// Returns the same value as [`smchash_seeded`](crate::smchash_seeded) over
+/// the little-endian byte representation of `data`, but reads the words
+/// directly. Every read in the algorithm lands on a word boundary when the
+/// length is a multiple of 8, and reading 8 little-endian bytes of `x` yields
+/// `x` itself, so the result is identical on little- and big-endian targets
+/// without any byte swapping.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_seeded, smchash_u64_slice};
+///
+/// let words = [1u64, 2, 3];
+/// let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+/// assert_eq!(smchash_u64_slice(&words, 7), smchash_seeded(&bytes, 7));
+/// ```
+#[must_use]
+pub fn smchash_u64_slice(data: &[u64], mut seed: u64) -> u64 {
+    let n = data.len();
+    let len = (n as u64) * 8;
+
+    if n <= 2 {
+        seed ^= mix(seed ^ SMC_SECRET[0], SMC_SECRET[1] ^ len);
+        let (a, b) = if n == 0 {
+            (0, 0)
+        } else {
+            (data[0], data[n - 1])
+        };
+        let (a, b) = mum_pair(a ^ SMC_SECRET[1], b ^ seed);
+        return mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ len);
+    }
+
+    seed ^= mix(seed ^ SMC_SECRET[2], SMC_SECRET[1]);
+    let mut p = data;
+    let mut i = n;
+
+    // Bulk: 16 words = 128 bytes
+    if n > 16 {
+        let mut lanes = [seed; 8];
+        while i > 16 {
+            for (k, lane) in lanes.iter_mut().enumerate() {
+                *lane = mix(p[2 * k] ^ SMC_SECRET[k], p[2 * k + 1] ^ *lane);
+            }
+            p = &p[16..];
+            i -= 16;
+        }
+        seed = fold(&lanes);
+    }
+
+    if i > 8 {
+        seed = mix(p[0] ^ SMC_SECRET[0], p[1] ^ seed);
+        seed = mix(p[2] ^ SMC_SECRET[1], p[3] ^ seed);
+        seed = mix(p[4] ^ SMC_SECRET[2], p[5] ^ seed);
+        seed = mix(p[6] ^ SMC_SECRET[3], p[7] ^ seed);
+        p = &p[8..];
+        i -= 8;
+    }
+    if i > 4 {
+        seed = mix(p[0] ^ SMC_SECRET[0], p[1] ^ seed);
+        seed = mix(p[2] ^ SMC_SECRET[1], p[3] ^ seed);
+        p = &p[4..];
+        i -= 4;
+    }
+    if i > 2 {
+        seed = mix(p[0] ^ SMC_SECRET[0], p[1] ^ seed);
+    }
+
+    let a = data[n - 2] ^ len;
+    let b = data[n - 1];
+    let (a, b) = mum_pair(a ^ SMC_SECRET[1], b ^ seed);
+    mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ len)
+}
+
+/// Hash two `u64` values, e.g. grid coordinates.
+///
+/// Same as `smchash_u64_slice(&[a, b], seed)`, and so the same as
+/// [`smchash_seeded`](crate::smchash_seeded) over the 16 little-endian bytes
+/// of `a` then `b`, with the 16-byte path written out for fixed arity.
+///
+/// # Example
+///
+/// ```rust
+/// use smchash::{smchash_seeded, smchash_u64x2};
+///
+/// let mut key = [0u8; 16];
+/// key[..8].copy_from_slice(&3u64.to_le_bytes());
+/// key[8..].copy_from_slice(&4u64.to_le_bytes());
+/// assert_eq!(smchash_u64x2(3, 4, 0), smchash_seeded(&key, 0));
+/// ```
+#[inline]
+#[must_use]
+pub fn smchash_u64x2(a: u64, b: u64, mut seed: u64) -> u64 {
+    seed ^= mix(seed ^ SMC_SECRET[0], SMC_SECRET[1] ^ 16);
+    let (a, b) = mum_pair(a ^ SMC_SECRET[1], b ^ seed);
+    mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ 16)
+}
+
+/// Hash three `u64` values, e.g. 3D grid coordinates.
+///
+/// Same as `smchash_u64_slice(&[a, b, c], seed)`, and so the same as
+/// [`smchash_seeded`](crate::smchash_seeded) over the 24 little-endian bytes
+/// of `a`, `b` then `c`.
+#[inline]
+#[must_use]
+pub fn smchash_u64x3(a: u64, b: u64, c: u64, mut seed: u64) -> u64 {
+    seed ^= mix(seed ^ SMC_SECRET[2], SMC_SECRET[1]);
+    seed = mix(a ^ SMC_SECRET[0], b ^ seed);
+    let (a, b) = mum_pair(b ^ 24 ^ SMC_SECRET[1], c ^ seed);
+    mix(a ^ SMC_SECRET[8], b ^ SMC_SECRET[1] ^ 24)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{smc_rand, smchash, smchash_seeded};
+
+    #[test]
+    fn test_u64_slice_matches_bytes() {
+        let mut rng = 11u64;
+        let words: alloc::vec::Vec<u64> = (0..100).map(|_| smc_rand(&mut rng)).collect();
+        for n in 0..words.len() {
+            let bytes: alloc::vec::Vec<u8> =
+                words[..n].iter().flat_map(|w| w.to_le_bytes()).collect();
+            assert_eq!(
+                smchash_u64_slice(&words[..n], SMC_SECRET[0]),
+                smchash(&bytes),
+                "n {}",
+                n
+            );
+            assert_eq!(
+                smchash_u64_slice(&words[..n], 42),
+                smchash_seeded(&bytes, 42)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixed_arity_matches_bytes() {
+        let mut rng = 3u64;
+        for _ in 0..1000 {
+            let (a, b, c, seed) = (
+                smc_rand(&mut rng),
+                smc_rand(&mut rng),
+                smc_rand(&mut rng),
+                smc_rand(&mut rng),
+            );
+            let mut bytes = [0u8; 24];
+            bytes[..8].copy_from_slice(&a.to_le_bytes());
+            bytes[8..16].copy_from_slice(&b.to_le_bytes());
+            bytes[16..].copy_from_slice(&c.to_le_bytes());
+            assert_eq!(
+                smchash_u64x2(a, b, seed),
+                smchash_seeded(&bytes[..16], seed)
+            );
+            assert_eq!(smchash_u64x3(a, b, c, seed), smchash_seeded(&bytes, seed));
+        }
+        assert_eq!(
+            smchash_u64x2(1, 2, SMC_SECRET[0]),
+            smchash_u64_slice(&[1, 2], SMC_SECRET[0])
+        );
+    }
+}